@@ -4,64 +4,1084 @@ use ink_lang as ink;
 
 #[ink::contract]
 mod erc20swap {
-    use ink_env::call::FromAccountId;
-    // can add `pub use self::erc20::Erc20` to avoid this
-    use erc20::erc20::Erc20 as Erc20;
+    use ::ink_prelude::vec::Vec;
+    use ::ink_lang::{
+        EmitEvent,
+        Env,
+        StaticEnv,
+    };
 
+    use ::erc20_basic::{
+        selectors::NEW as ERC20_NEW,
+        Data as Erc20Data,
+        Erc20EventEmit,
+        Erc20Impl,
+        CallResult,
+        Erc20Interface,
+        Erc20Ref,
+        Result as Erc20Result,
+    };
+    use ::owner::{
+        Data as OwnerData,
+        EventEmit as OwnerEventEmit,
+        Impl as OwnerImpl,
+    };
+
+    /// The traded token, any contract with the standard ERC-20 selectors.
+    type Token = Erc20Ref<::ink_env::DefaultEnvironment>;
+
+    /// Selector of `swap_exact_in_to`, pools call each other by it for token-to-token
+    /// swaps.
+    const SWAP_EXACT_IN_TO: [u8; 4] = [0x5a, 0x3c, 0x0f, 0x21];
+
+    /// A constant-product pool between the native balance and an ERC-20 token.
+    ///
+    /// The pool is itself an ERC-20 token of the liquidity shares.
     #[ink(storage)]
     pub struct ERC20Swap {
-        value: bool,
         token_account_id: AccountId,
+        /// Native balance held by the pool.
+        reserve_native: Balance,
+        /// Tokens held by the pool.
+        reserve_token: Balance,
+        /// The liquidity shares, minted to providers on `add_liquidity`.
+        data_erc20: ::contract::Namespaced<Erc20Data<ERC20Swap>>,
+        /// The owner, who can set the token while the pool is empty.
+        data_owner: ::contract::Namespaced<OwnerData<ERC20Swap>>,
+    }
+
+    /// Event emitted when a liquidity share transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` liquidity shares from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when a swap occurs.
+    #[ink(event)]
+    pub struct Swap {
+        #[ink(topic)]
+        sender: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        native_in: Balance,
+        token_in: Balance,
+        native_out: Balance,
+        token_out: Balance,
+    }
+
+    /// Event emitted when liquidity is added to the pool.
+    #[ink(event)]
+    pub struct AddLiquidity {
+        #[ink(topic)]
+        provider: AccountId,
+        native_amount: Balance,
+        token_amount: Balance,
+        liquidity: Balance,
+    }
+
+    /// Event emitted when liquidity is removed from the pool.
+    #[ink(event)]
+    pub struct RemoveLiquidity {
+        #[ink(topic)]
+        provider: AccountId,
+        native_amount: Balance,
+        token_amount: Balance,
+        liquidity: Balance,
+    }
+
+    /// The ERC20Swap error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the amount put into the pool is zero.
+        ZeroAmount,
+        /// Returned if native balance and tokens are sold in the same swap.
+        InvalidSwap,
+        /// Returned if the pool or the provider has not enough liquidity.
+        InsufficientLiquidity,
+        /// Returned if the amounts are worse than the limits given by the caller.
+        SlippageExceeded,
+        /// Returned if an amount calculation overflows.
+        Overflow,
+        /// Returned if the token refused to transfer tokens.
+        TokenTransferFailed,
+        /// Returned if the call to the token failed, e.g. it is not a token or it
+        /// trapped.
+        TokenCallFailed,
+        /// Returned if the call to the other pool of a token-to-token swap failed.
+        PoolCallFailed,
+        /// Returned if the caller is not the owner.
+        NotOwner,
+        /// Returned if the token is set while the pool holds liquidity.
+        PoolNotEmpty,
+        /// Returned if the stored data is newer than the code.
+        MigrationFailed,
+    }
+
+    /// The ERC20Swap result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    // TODO: Make by macro
+    use ::contract::{
+        Migrate,
+        Module,
+    };
+    impl ::contract::Env for ERC20Swap {
+        type AccountId = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::AccountId;
+        type Balance = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Balance;
+        type Hash = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Hash;
+        type Timestamp = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Timestamp;
+        type BlockNumber = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::BlockNumber;
+    }
+
+    impl ::contract::EnvAccess<ERC20Swap> for ERC20Swap {
+        fn caller() -> <ERC20Swap as ::contract::Env>::AccountId {
+            Self::env().caller()
+        }
+
+        fn transferred_balance() -> <ERC20Swap as ::contract::Env>::Balance {
+            Self::env().transferred_balance()
+        }
+    }
+
+    impl ::contract::Module<Erc20Data<ERC20Swap>> for ERC20Swap {
+        fn get_module(&self) -> &Erc20Data<ERC20Swap> {
+            &self.data_erc20
+        }
+
+        fn get_module_mut(&mut self) -> &mut Erc20Data<ERC20Swap> {
+            &mut self.data_erc20
+        }
+    }
+
+    impl ::contract::Module<OwnerData<ERC20Swap>> for ERC20Swap {
+        fn get_module(&self) -> &OwnerData<ERC20Swap> {
+            &self.data_owner
+        }
+
+        fn get_module_mut(&mut self) -> &mut OwnerData<ERC20Swap> {
+            &mut self.data_owner
+        }
+    }
+    // TODO: Make by macro
+
+    // TODO: Event in ink!
+    impl Erc20EventEmit<ERC20Swap> for ERC20Swap {
+        fn emit_event_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
+        ) {
+            self.env().emit_event(Transfer { from, to, value });
+        }
+
+        fn emit_event_approval(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) {
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+        }
     }
 
+    impl OwnerEventEmit<ERC20Swap> for ERC20Swap {}
+    // TODO: Event in ink!
+
     impl ERC20Swap {
         #[ink(constructor)]
-        pub fn new(init_value: bool, account_id: AccountId) -> Self {
-            Self { 
-                value: init_value,
+        pub fn new(account_id: AccountId) -> Self {
+            let mut instance = Self {
                 token_account_id: account_id,
-            }
+                reserve_native: 0,
+                reserve_token: 0,
+                data_erc20: ::contract::Namespaced::init(Erc20Data::new()),
+                data_owner: ::contract::Namespaced::init(OwnerData::new()),
+            };
+            OwnerImpl::new_impl(&mut instance);
+            instance
+        }
+
+        /// Instantiates an `Erc20` token of `code_hash` with `initial_supply` tokens,
+        /// which are given to the caller, and creates a pool for it.
+        ///
+        /// The transferred balance is the endowment of the token.
+        #[ink(constructor)]
+        pub fn new_with_token(
+            code_hash: Hash,
+            initial_supply: Balance,
+            salt: Vec<u8>,
+        ) -> Self {
+            let mut token: Token =
+                ::contract::instantiate::<ink_env::DefaultEnvironment, _, _>(
+                    code_hash,
+                    ERC20_NEW,
+                    initial_supply,
+                    Self::env().transferred_balance(),
+                    &salt,
+                )
+                .expect("Cannot instantiate the token");
+            // The pool called the constructor, so it holds the initial supply.
+            token
+                .transfer(Self::env().caller(), initial_supply)
+                .ok()
+                .and_then(|result| result.ok())
+                .expect("Cannot transfer the initial supply");
+            Self::new(token.account_id())
         }
 
         #[ink(constructor)]
         pub fn default() -> Self {
-            Self::new(Default::default(), AccountId::default())
+            Self::new(AccountId::default())
         }
 
+        /// Returns the token traded by the pool.
         #[ink(message)]
-        pub fn flip(&mut self) {
-            self.value = !self.value;
+        pub fn token(&self) -> AccountId {
+            self.token_account_id
         }
 
+        /// Sets the traded token, only the owner can do this while the pool is empty.
         #[ink(message)]
-        pub fn get(&self) -> AccountId {
-            self.token_account_id
+        pub fn set_token(&mut self, account_id: AccountId) -> Result<()> {
+            if OwnerImpl::owner(self) != &Some(self.env().caller()) {
+                return Err(Error::NotOwner)
+            }
+            if self.total_supply() != 0 {
+                return Err(Error::PoolNotEmpty)
+            }
+            self.token_account_id = account_id;
+            Ok(())
+        }
+
+        /// Migrates the module data to the layouts of the current code, the owner
+        /// calls this after an upgrade.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<()> {
+            if OwnerImpl::owner(self) != &Some(self.env().caller()) {
+                return Err(Error::NotOwner)
+            }
+            self.data_erc20
+                .migrate()
+                .map_err(|_| Error::MigrationFailed)?;
+            self.data_owner
+                .migrate()
+                .map_err(|_| Error::MigrationFailed)?;
+            Ok(())
+        }
+
+        /// Returns the owner of the pool.
+        #[ink(message, selector = "0x07FCD0B1")]
+        pub fn get_owner(&self) -> Option<AccountId> {
+            *OwnerImpl::owner(self)
+        }
+
+        /// Transfers the ownership of the pool to `new_owner`, only the owner can do
+        /// this.
+        #[ink(message, selector = "0x107E33EA")]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) {
+            OwnerImpl::transfer_ownership(self, new_owner);
+        }
+
+        /// Leaves the pool without owner, only the owner can do this.
+        #[ink(message, selector = "0x8C90065B")]
+        pub fn renounce_ownership(&mut self) {
+            OwnerImpl::renounce_ownership(self);
+        }
+
+        /// Returns the token balance of `owner`.
+        #[ink(message)]
+        pub fn token_balance_of(&self, owner: AccountId) -> Result<Balance> {
+            let token = Token::from_account_id(self.token_account_id);
+            token.balance_of(owner).map_err(|_| Error::TokenCallFailed)
         }
 
+        /// Returns the native and token reserves of the pool.
         #[ink(message)]
+        pub fn reserves(&self) -> (Balance, Balance) {
+            (self.reserve_native, self.reserve_token)
+        }
+
+        /// Returns the total liquidity shares of the pool.
+        #[ink(message, selector = "0xDB6375A8")]
+        pub fn total_supply(&self) -> Balance {
+            self.get_module().get_total_supply()
+        }
+
+        /// Returns the liquidity shares of `owner`.
+        #[ink(message, selector = "0x0F755A56")]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
-            let token = <Erc20>::from_account_id(self.token_account_id);
-            return token.balance_of(owner);
+            self.get_module().get_balance(owner)
+        }
+
+        /// Returns the amount of liquidity shares which `spender` is still allowed to
+        /// withdraw from `owner`.
+        #[ink(message, selector = "0x6A00165E")]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.get_module().get_allowance(owner, spender)
+        }
+
+        /// Transfers `value` liquidity shares from the caller's account to `to`.
+        #[ink(message, selector = "0x84A15DA1")]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Erc20Result<()> {
+            self.transfer_impl(to, value)
+        }
+
+        /// Allows `spender` to withdraw up to `value` liquidity shares from the caller's
+        /// account.
+        #[ink(message, selector = "0x681266A0")]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Erc20Result<()> {
+            self.approve_impl(spender, value)
+        }
+
+        /// Transfers `value` liquidity shares on the behalf of `from` to `to`.
+        #[ink(message, selector = "0x0B396F18")]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Erc20Result<()> {
+            self.transfer_from_impl(from, to, value)
+        }
+
+        /// Returns the output of selling `amount_in` native balance, or tokens if
+        /// `native_in` is `false`, at the current reserves.
+        #[ink(message)]
+        pub fn get_amount_out(
+            &self,
+            amount_in: Balance,
+            native_in: bool,
+        ) -> Result<Balance> {
+            if native_in {
+                Self::amount_out(amount_in, self.reserve_native, self.reserve_token)
+            } else {
+                Self::amount_out(amount_in, self.reserve_token, self.reserve_native)
+            }
+        }
+
+        /// Adds the transferred native balance and the matching amount of tokens to
+        /// the pool, the tokens are taken by `transfer_from` so the pool must be
+        /// approved first.
+        ///
+        /// The first provider sets the price by depositing `max_tokens`.
+        ///
+        /// Returns the minted liquidity shares, on error the native balance is
+        /// refunded.
+        #[ink(message, payable)]
+        pub fn add_liquidity(
+            &mut self,
+            max_tokens: Balance,
+            min_liquidity: Balance,
+        ) -> Result<Balance> {
+            let caller = self.env().caller();
+            let native_amount = self.env().transferred_balance();
+            let result =
+                self.add_liquidity_from(caller, native_amount, max_tokens, min_liquidity);
+            self.refund_on_error(caller, native_amount, result)
+        }
+
+        /// Burns `liquidity` shares of the caller and pays out its part of both
+        /// reserves.
+        ///
+        /// Returns the native balance and the tokens paid out.
+        #[ink(message)]
+        pub fn remove_liquidity(
+            &mut self,
+            liquidity: Balance,
+            min_native: Balance,
+            min_tokens: Balance,
+        ) -> Result<(Balance, Balance)> {
+            let caller = self.env().caller();
+            if liquidity == 0 {
+                return Err(Error::ZeroAmount)
+            }
+            if self.balance_of(caller) < liquidity {
+                return Err(Error::InsufficientLiquidity)
+            }
+            let native_amount =
+                Self::share(liquidity, self.reserve_native, self.total_supply())?;
+            let token_amount =
+                Self::share(liquidity, self.reserve_token, self.total_supply())?;
+            if native_amount < min_native || token_amount < min_tokens {
+                return Err(Error::SlippageExceeded)
+            }
+            let reserve_native = self
+                .reserve_native
+                .checked_sub(native_amount)
+                .ok_or(Error::Overflow)?;
+            let reserve_token = self
+                .reserve_token
+                .checked_sub(token_amount)
+                .ok_or(Error::Overflow)?;
+
+            self.pay_tokens(caller, token_amount)?;
+
+            self.burn_impl(caller, liquidity)
+                .map_err(|_| Error::InsufficientLiquidity)?;
+            self.reserve_native = reserve_native;
+            self.reserve_token = reserve_token;
+            self.pay_native(caller, native_amount);
+            self.env().emit_event(RemoveLiquidity {
+                provider: caller,
+                native_amount,
+                token_amount,
+                liquidity,
+            });
+            Ok((native_amount, token_amount))
+        }
+
+        /// Swaps the transferred native balance for tokens, or `token_in` tokens for
+        /// native balance if nothing is transferred, paying out to the caller.
+        ///
+        /// Returns the amount paid out, on error the native balance is refunded.
+        #[ink(message, payable)]
+        pub fn swap_exact_in(
+            &mut self,
+            token_in: Balance,
+            min_amount_out: Balance,
+        ) -> Result<Balance> {
+            let caller = self.env().caller();
+            self.swap_exact_in_to(token_in, min_amount_out, caller)
+        }
+
+        /// Swaps like `swap_exact_in`, paying out to `to`.
+        #[ink(message, payable, selector = "0x5A3C0F21")]
+        pub fn swap_exact_in_to(
+            &mut self,
+            token_in: Balance,
+            min_amount_out: Balance,
+            to: AccountId,
+        ) -> Result<Balance> {
+            let caller = self.env().caller();
+            let native_in = self.env().transferred_balance();
+            let result = self.swap(caller, native_in, token_in, min_amount_out, to);
+            self.refund_on_error(caller, native_in, result)
+        }
+
+        /// Sells `token_in` tokens for native balance and swaps it in the ERC20Swap
+        /// `pool` for its tokens, which are paid out to the caller.
+        ///
+        /// Returns the amount of tokens bought in `pool`.
+        #[ink(message)]
+        pub fn swap_token_to_token(
+            &mut self,
+            token_in: Balance,
+            min_amount_out: Balance,
+            pool: AccountId,
+        ) -> Result<Balance> {
+            let caller = self.env().caller();
+            let native_out =
+                Self::amount_out(token_in, self.reserve_token, self.reserve_native)?;
+            let reserve_token = self
+                .reserve_token
+                .checked_add(token_in)
+                .ok_or(Error::Overflow)?;
+            let reserve_native = self
+                .reserve_native
+                .checked_sub(native_out)
+                .ok_or(Error::Overflow)?;
+            self.take_tokens(caller, token_in)?;
+
+            let result = ::contract::invoke::<ink_env::DefaultEnvironment, _, _>(
+                &pool,
+                SWAP_EXACT_IN_TO,
+                (0 as Balance, min_amount_out, caller),
+                native_out,
+            )
+            .unwrap_or(Err(Error::PoolCallFailed));
+            let token_out = match result {
+                Ok(token_out) => token_out,
+                Err(error) => {
+                    // Give the tokens back, the native balance of a failed call stays
+                    // in the pool.
+                    self.pay_tokens(caller, token_in)?;
+                    return Err(error)
+                }
+            };
+
+            self.reserve_token = reserve_token;
+            self.reserve_native = reserve_native;
+            self.env().emit_event(Swap {
+                sender: caller,
+                to: pool,
+                native_in: 0,
+                token_in,
+                native_out,
+                token_out: 0,
+            });
+            Ok(token_out)
+        }
+
+        fn add_liquidity_from(
+            &mut self,
+            provider: AccountId,
+            native_amount: Balance,
+            max_tokens: Balance,
+            min_liquidity: Balance,
+        ) -> Result<Balance> {
+            if native_amount == 0 || max_tokens == 0 {
+                return Err(Error::ZeroAmount)
+            }
+            let (token_amount, liquidity) = if self.total_supply() == 0 {
+                (max_tokens, native_amount)
+            } else {
+                let token_amount =
+                    Self::share(native_amount, self.reserve_token, self.reserve_native)?
+                        .checked_add(1)
+                        .ok_or(Error::Overflow)?;
+                let liquidity =
+                    Self::share(native_amount, self.total_supply(), self.reserve_native)?;
+                (token_amount, liquidity)
+            };
+            if token_amount > max_tokens || liquidity < min_liquidity {
+                return Err(Error::SlippageExceeded)
+            }
+            let reserve_native = self
+                .reserve_native
+                .checked_add(native_amount)
+                .ok_or(Error::Overflow)?;
+            let reserve_token = self
+                .reserve_token
+                .checked_add(token_amount)
+                .ok_or(Error::Overflow)?;
+            self.take_tokens(provider, token_amount)?;
+
+            if self.mint_impl(provider, liquidity).is_err() {
+                // Give the tokens back, minting fails only if the shares overflow.
+                self.pay_tokens(provider, token_amount)?;
+                return Err(Error::Overflow)
+            }
+            self.reserve_native = reserve_native;
+            self.reserve_token = reserve_token;
+            self.env().emit_event(AddLiquidity {
+                provider,
+                native_amount,
+                token_amount,
+                liquidity,
+            });
+            Ok(liquidity)
+        }
+
+        fn swap(
+            &mut self,
+            sender: AccountId,
+            native_in: Balance,
+            token_in: Balance,
+            min_amount_out: Balance,
+            to: AccountId,
+        ) -> Result<Balance> {
+            if native_in > 0 && token_in > 0 {
+                return Err(Error::InvalidSwap)
+            }
+            let (native_out, token_out) = if native_in > 0 {
+                let token_out =
+                    Self::amount_out(native_in, self.reserve_native, self.reserve_token)?;
+                (0, token_out)
+            } else {
+                let native_out =
+                    Self::amount_out(token_in, self.reserve_token, self.reserve_native)?;
+                (native_out, 0)
+            };
+            let amount_out = native_out + token_out;
+            if amount_out < min_amount_out {
+                return Err(Error::SlippageExceeded)
+            }
+            let reserve_native = self
+                .reserve_native
+                .checked_add(native_in)
+                .and_then(|reserve| reserve.checked_sub(native_out))
+                .ok_or(Error::Overflow)?;
+            let reserve_token = self
+                .reserve_token
+                .checked_add(token_in)
+                .and_then(|reserve| reserve.checked_sub(token_out))
+                .ok_or(Error::Overflow)?;
+            if token_in > 0 {
+                self.take_tokens(sender, token_in)?;
+            }
+            if token_out > 0 {
+                self.pay_tokens(to, token_out)?;
+            }
+
+            self.reserve_native = reserve_native;
+            self.reserve_token = reserve_token;
+            if native_out > 0 {
+                self.pay_native(to, native_out);
+            }
+            self.env().emit_event(Swap {
+                sender,
+                to,
+                native_in,
+                token_in,
+                native_out,
+                token_out,
+            });
+            Ok(amount_out)
+        }
+
+        /// Returns the output of selling `amount_in` into a pool with the reserves
+        /// `reserve_in` and `reserve_out`, with a fee of 0.3%.
+        fn amount_out(
+            amount_in: Balance,
+            reserve_in: Balance,
+            reserve_out: Balance,
+        ) -> Result<Balance> {
+            if amount_in == 0 {
+                return Err(Error::ZeroAmount)
+            }
+            if reserve_in == 0 || reserve_out == 0 {
+                return Err(Error::InsufficientLiquidity)
+            }
+            let amount_in_with_fee = amount_in.checked_mul(997).ok_or(Error::Overflow)?;
+            let numerator = amount_in_with_fee
+                .checked_mul(reserve_out)
+                .ok_or(Error::Overflow)?;
+            let denominator = reserve_in
+                .checked_mul(1000)
+                .and_then(|reserve| reserve.checked_add(amount_in_with_fee))
+                .ok_or(Error::Overflow)?;
+            Ok(numerator / denominator)
+        }
+
+        /// Returns `amount * numerator / denominator`.
+        fn share(
+            amount: Balance,
+            numerator: Balance,
+            denominator: Balance,
+        ) -> Result<Balance> {
+            if denominator == 0 {
+                return Err(Error::InsufficientLiquidity)
+            }
+            amount
+                .checked_mul(numerator)
+                .map(|product| product / denominator)
+                .ok_or(Error::Overflow)
+        }
+
+        /// Takes `value` tokens of `from` into the pool.
+        fn take_tokens(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let mut token = Token::from_account_id(self.token_account_id);
+            let result = token.transfer_from(from, self.env().account_id(), value);
+            Self::token_result(result)
+        }
+
+        /// Pays out `value` tokens of the pool.
+        fn pay_tokens(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let mut token = Token::from_account_id(self.token_account_id);
+            let result = token.transfer(to, value);
+            Self::token_result(result)
+        }
+
+        /// Maps the result of a token transfer to the pool errors.
+        fn token_result(result: CallResult<Erc20Result<()>>) -> Result<()> {
+            match result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(_)) => Err(Error::TokenTransferFailed),
+                Err(_) => Err(Error::TokenCallFailed),
+            }
+        }
+
+        /// Pays out `value` native balance of the pool, it is done after the state
+        /// changes so a failure traps to revert the call.
+        fn pay_native(&mut self, to: AccountId, value: Balance) {
+            self.env()
+                .transfer(to, value)
+                .expect("pool does not hold its native reserve");
+        }
+
+        /// Sends the transferred native balance back to `caller` if `result` is an
+        /// error.
+        fn refund_on_error<T>(
+            &mut self,
+            caller: AccountId,
+            value: Balance,
+            result: Result<T>,
+        ) -> Result<T> {
+            if result.is_err() && value > 0 {
+                self.pay_native(caller, value);
+            }
+            result
         }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use ::contract_test::{
+            as_caller,
+            balance_of,
+            contract_id,
+            default_accounts,
+            set_balance,
+            with_value,
+        };
         use ink_lang as ink;
 
         #[ink::test]
         fn default_works() {
             let swapper = ERC20Swap::default();
-            assert_eq!(swapper.get(), false);
+            assert_eq!(swapper.token(), AccountId::default());
+            assert_eq!(swapper.reserves(), (0, 0));
+            assert_eq!(swapper.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let token = AccountId::from([0x07; 32]);
+            let swapper = ERC20Swap::new(token);
+            assert_eq!(swapper.token(), token);
+            assert_eq!(
+                swapper.get_amount_out(10, true),
+                Err(Error::InsufficientLiquidity)
+            );
+        }
+
+        #[ink::test]
+        fn set_token_works() {
+            let accounts = default_accounts::<ERC20Swap>();
+            let token = AccountId::from([0x07; 32]);
+            let mut swapper = ERC20Swap::default();
+            assert_eq!(swapper.get_owner(), Some(accounts.alice));
+
+            // Bob is not the owner.
+            assert_eq!(
+                as_caller(accounts.bob, || swapper.set_token(token)),
+                Err(Error::NotOwner)
+            );
+            assert_eq!(swapper.token(), AccountId::default());
+
+            assert_eq!(swapper.set_token(token), Ok(()));
+            assert_eq!(swapper.token(), token);
+        }
+
+        #[ink::test]
+        fn migrate_works() {
+            let accounts = default_accounts::<ERC20Swap>();
+            let mut swapper = ERC20Swap::default();
+
+            // Bob is not the owner.
+            assert_eq!(
+                as_caller(accounts.bob, || swapper.migrate()),
+                Err(Error::NotOwner)
+            );
+
+            assert_eq!(swapper.migrate(), Ok(()));
+        }
+
+        #[ink::test]
+        fn amount_out_works() {
+            // 1000 * 997 * 10000 / (10000 * 1000 + 1000 * 997)
+            assert_eq!(ERC20Swap::amount_out(1000, 10000, 10000), Ok(906));
+            assert_eq!(ERC20Swap::amount_out(0, 10000, 10000), Err(Error::ZeroAmount));
+            assert_eq!(
+                ERC20Swap::amount_out(Balance::MAX, 1, 1),
+                Err(Error::Overflow)
+            );
         }
 
         #[ink::test]
-        fn it_works() {
-            let mut swapper = ERC20Swap::new(false, AccountId::default());
-            assert_eq!(swapper.get(), false);
-            swapper.flip();
-            assert_eq!(swapper.get(), true);
+        fn add_liquidity_requires_value() {
+            let mut swapper = ERC20Swap::new(AccountId::from([0x07; 32]));
+            assert_eq!(swapper.add_liquidity(100, 0), Err(Error::ZeroAmount));
+            assert_eq!(swapper.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn remove_liquidity_requires_shares() {
+            let accounts = default_accounts::<ERC20Swap>();
+            let mut swapper = ERC20Swap::new(AccountId::from([0x07; 32]));
+            assert_eq!(swapper.balance_of(accounts.alice), 0);
+            assert_eq!(swapper.remove_liquidity(0, 0, 0), Err(Error::ZeroAmount));
+            assert_eq!(
+                swapper.remove_liquidity(10, 0, 0),
+                Err(Error::InsufficientLiquidity)
+            );
+        }
+
+        #[ink::test]
+        fn liquidity_shares_are_erc20() {
+            let accounts = default_accounts::<ERC20Swap>();
+            let mut swapper = ERC20Swap::new(AccountId::from([0x07; 32]));
+            assert_eq!(swapper.total_supply(), 0);
+            assert_eq!(
+                swapper.transfer(accounts.bob, 10),
+                Err(erc20_basic::Error::InsufficientBalance)
+            );
+            assert_eq!(swapper.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(swapper.allowance(accounts.alice, accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn failed_swap_refunds_value() {
+            let accounts = default_accounts::<ERC20Swap>();
+            let mut swapper = ERC20Swap::new(AccountId::from([0x07; 32]));
+            // Bob sells 100 native balance and tokens at once.
+            assert_failed_swap_refunds(accounts.bob, || {
+                assert_eq!(swapper.swap_exact_in(10, 0), Err(Error::InvalidSwap))
+            });
+            // The pool is empty.
+            assert_failed_swap_refunds(accounts.bob, || {
+                assert_eq!(
+                    swapper.swap_exact_in(0, 0),
+                    Err(Error::InsufficientLiquidity)
+                )
+            });
+            assert_eq!(swapper.reserves(), (0, 0));
+        }
+
+        /// Runs the failing swap `f` of `caller` with a value of 100, and checks that
+        /// the value is refunded.
+        fn assert_failed_swap_refunds(caller: AccountId, f: impl FnOnce()) {
+            // The transferred value is held by the contract during the call.
+            set_balance(contract_id(), 100);
+            let caller_balance = balance_of(caller);
+            as_caller(caller, || with_value(100, f));
+            assert_eq!(balance_of(caller), caller_balance + 100);
+            assert_eq!(balance_of(contract_id()), 0);
+        }
+    }
+
+    /// Ports of the cross-contract tests of `erc20swap.test.ts`, the pools and the
+    /// tokens are hosted by the simulator of `contract_test`.
+    #[cfg(all(test, feature = "test-utils"))]
+    mod simulator_tests {
+        use super::*;
+        use ::contract_test::{
+            decode_input,
+            default_accounts,
+            erc20_dispatch,
+            Reply,
+            Simulator,
+        };
+        use ::erc20::erc20::Erc20;
+        use scale::Encode;
+
+        const INITIAL_SUPPLY: Balance = 1_000_000;
+
+        /// Dispatches the calls of other pools, the only message a pool calls.
+        fn dispatch(
+            pool: &mut ERC20Swap,
+            selector: [u8; 4],
+            input: &mut &[u8],
+        ) -> Reply {
+            match selector {
+                SWAP_EXACT_IN_TO => {
+                    let (token_in, min_amount_out, to) = decode_input(input)?;
+                    Ok(pool.swap_exact_in_to(token_in, min_amount_out, to).encode())
+                }
+                _ => Err(::ink_env::Error::CalleeTrapped),
+            }
+        }
+
+        fn alice() -> AccountId {
+            default_accounts::<ERC20Swap>().alice
+        }
+
+        /// Deploys a token of Alice and a pool for it, returns their accounts.
+        fn deploy(simulator: &mut Simulator) -> (AccountId, AccountId) {
+            let token = simulator.deploy(
+                alice(),
+                || Erc20::new(INITIAL_SUPPLY),
+                erc20_dispatch!(Erc20),
+            );
+            let pool = simulator.deploy(alice(), move || ERC20Swap::new(token), dispatch);
+            (token, pool)
+        }
+
+        /// Adds 10000 native balance and 1000 tokens of Alice to `pool`.
+        fn add_liquidity(simulator: &Simulator, token: AccountId, pool: AccountId) {
+            simulator
+                .call(token, alice(), 0, move |token: &mut Erc20| {
+                    token.approve(pool, 1000)
+                })
+                .expect("Cannot approve the pool");
+            let liquidity =
+                simulator.try_call(pool, alice(), 10000, |pool: &mut ERC20Swap| {
+                    pool.add_liquidity(1000, 0)
+                });
+            assert_eq!(liquidity, Ok(10000));
+        }
+
+        fn token_balance(simulator: &Simulator, token: AccountId) -> Balance {
+            simulator.call(token, alice(), 0, |token: &mut Erc20| {
+                token.balance_of(alice())
+            })
+        }
+
+        fn reserves(simulator: &Simulator, pool: AccountId) -> (Balance, Balance) {
+            simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| pool.reserves())
+        }
+
+        #[test]
+        fn initial() {
+            let mut simulator = Simulator::new();
+            let (token, pool) = deploy(&mut simulator);
+
+            let pool_token =
+                simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| pool.token());
+            assert_eq!(pool_token, token);
+            assert_eq!(token_balance(&simulator, token), INITIAL_SUPPLY);
+            // The pool reads the balance from the token.
+            let balance = simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                pool.token_balance_of(alice())
+            });
+            assert_eq!(balance, Ok(INITIAL_SUPPLY));
+        }
+
+        #[test]
+        fn swaps_native_balance_for_tokens() {
+            let mut simulator = Simulator::new();
+            let (token, pool) = deploy(&mut simulator);
+
+            add_liquidity(&simulator, token, pool);
+            let shares = simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                pool.balance_of(alice())
+            });
+            assert_eq!(shares, 10000);
+            assert_eq!(reserves(&simulator, pool), (10000, 1000));
+            // The constructor, the approval and the transfer to the pool.
+            assert_eq!(simulator.events(token).len(), 3);
+
+            // Buy tokens with native balance.
+            let token_out = simulator
+                .call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                    pool.get_amount_out(1000, true)
+                })
+                .expect("Cannot get the amount out");
+            assert_eq!(token_out, 90);
+            let swapped =
+                simulator.try_call(pool, alice(), 1000, |pool: &mut ERC20Swap| {
+                    pool.swap_exact_in(0, 1)
+                });
+            assert_eq!(swapped, Ok(token_out));
+            assert_eq!(
+                token_balance(&simulator, token),
+                INITIAL_SUPPLY - 1000 + token_out
+            );
+            assert_eq!(reserves(&simulator, pool), (11000, 1000 - token_out));
+        }
+
+        #[test]
+        fn swaps_token_to_token() {
+            let mut simulator = Simulator::new();
+            let (token_in, pool_in) = deploy(&mut simulator);
+            let (token_out, pool_out) = deploy(&mut simulator);
+            add_liquidity(&simulator, token_in, pool_in);
+            add_liquidity(&simulator, token_out, pool_out);
+
+            let native = simulator
+                .call(pool_in, alice(), 0, |pool: &mut ERC20Swap| {
+                    pool.get_amount_out(100, false)
+                })
+                .expect("Cannot get the amount out");
+            let expected = simulator
+                .call(pool_out, alice(), 0, move |pool: &mut ERC20Swap| {
+                    pool.get_amount_out(native, true)
+                })
+                .expect("Cannot get the amount out");
+
+            simulator
+                .call(token_in, alice(), 0, move |token: &mut Erc20| {
+                    token.approve(pool_in, 100)
+                })
+                .expect("Cannot approve the pool");
+            // `pool_in` pays the native balance to `pool_out`, which pays the tokens
+            // out to Alice.
+            let swapped =
+                simulator.try_call(pool_in, alice(), 0, move |pool: &mut ERC20Swap| {
+                    pool.swap_token_to_token(100, 1, pool_out)
+                });
+            assert_eq!(swapped, Ok(expected));
+            assert_eq!(
+                token_balance(&simulator, token_in),
+                INITIAL_SUPPLY - 1000 - 100
+            );
+            assert_eq!(
+                token_balance(&simulator, token_out),
+                INITIAL_SUPPLY - 1000 + expected
+            );
+            assert_eq!(reserves(&simulator, pool_in), (10000 - native, 1100));
+            assert_eq!(
+                reserves(&simulator, pool_out),
+                (10000 + native, 1000 - expected)
+            );
+        }
+
+        #[test]
+        fn failed_pool_call_refunds_tokens() {
+            let mut simulator = Simulator::new();
+            let (token, pool) = deploy(&mut simulator);
+            add_liquidity(&simulator, token, pool);
+            simulator
+                .call(token, alice(), 0, move |token: &mut Erc20| {
+                    token.approve(pool, 200)
+                })
+                .expect("Cannot approve the pool");
+
+            // The token traps on the selector of `swap_exact_in_to`, and the pool
+            // traps when it is called again during the swap.
+            for other in [token, pool].iter().copied() {
+                let swapped =
+                    simulator.call(pool, alice(), 0, move |pool: &mut ERC20Swap| {
+                        pool.swap_token_to_token(100, 1, other)
+                    });
+                assert_eq!(swapped, Err(Error::PoolCallFailed));
+            }
+            assert_eq!(token_balance(&simulator, token), INITIAL_SUPPLY - 1000);
+            assert_eq!(reserves(&simulator, pool), (10000, 1000));
+        }
+
+        #[test]
+        fn trapped_call_rolls_back() {
+            let mut simulator = Simulator::new();
+            let (token, pool) = deploy(&mut simulator);
+            add_liquidity(&simulator, token, pool);
+
+            let trapped = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                    pool.transfer(default_accounts::<ERC20Swap>().bob, 10)
+                        .expect("Cannot transfer the shares");
+                    panic!("trapped after the transfer");
+                })
+            }));
+            assert!(trapped.is_err());
+            let shares = simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                pool.balance_of(alice())
+            });
+            assert_eq!(shares, 10000);
+
+            // An error rolls back `try_call` too.
+            let transferred =
+                simulator.try_call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                    pool.transfer(default_accounts::<ERC20Swap>().bob, 10)?;
+                    Err::<(), _>(erc20_basic::Error::InsufficientBalance)
+                });
+            assert_eq!(transferred, Err(erc20_basic::Error::InsufficientBalance));
+            let shares = simulator.call(pool, alice(), 0, |pool: &mut ERC20Swap| {
+                pool.balance_of(alice())
+            });
+            assert_eq!(shares, 10000);
         }
     }
 }
@@ -10,16 +10,27 @@ async function run() {
   await api.isReady;
 
   const signer = createSigner(keyring.createFromUri(uri));
-  const contractFactory = await getContractFactory('erc20', signer);
+  const erc20Factory = await getContractFactory('erc20', signer);
+  const contractFactory = await getContractFactory('erc20swap', signer);
 
   const balance = await api.query.system.account(signer.address);
 
   console.log('Balance: ', balance.toHuman());
 
-  const contract = await contractFactory.deployed('new', '1000000', {
-    gasLimit: '200000000000',
-    value: '100000000000'
-  });
+  // Upload the code of the token, the swap contract instantiates it.
+  await erc20Factory.putCode();
+  const codeHash = erc20Factory.abi.project.source.wasmHash.toHex();
+
+  const contract = await contractFactory.deployed(
+    'newWithToken',
+    codeHash,
+    '1000000',
+    '0x00',
+    {
+      gasLimit: '200000000000',
+      value: '100000000000'
+    }
+  );
 
   console.log('');
   console.log(
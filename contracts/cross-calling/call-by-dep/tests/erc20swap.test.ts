@@ -31,7 +31,7 @@ describe('ERC20Swap', () => {
 
     // erc20swap
     const contractFactory = await getContractFactory('erc20swap', sender);
-    const contract = await contractFactory.deploy('new', 'true', contractErc20.address.toString());
+    const contract = await contractFactory.deploy('new', contractErc20.address.toString());
     const abi = artifacts.readArtifact('erc20swap');
 
     const receiver = await getRandomSigner();
@@ -40,13 +40,61 @@ describe('ERC20Swap', () => {
 
   it('initial', async () => {
     const {sender, contract, abi, contractErc20, receiver, Alice, one} = await setup();
-    const result = await contract.query.get();
+    const result = await contract.query.token();
     expect(result.output).to.equal(contractErc20.address);
 
     const resultBalance = await contractErc20.query.balanceOf(sender.address);
     expect(resultBalance.output).to.equal(1000000);
 
-    const resultBalanceProxy = await contract.query.balanceOf(sender.address);
-    expect(resultBalanceProxy.output).to.equal(1000000);
+    const resultBalanceProxy = await contract.query.tokenBalanceOf(sender.address);
+    expect(resultBalanceProxy.output?.toJSON()).to.eql({ ok: 1000000 });
+  });
+
+  it('swaps native balance and tokens', async () => {
+    const { sender, contract, contractErc20 } = await setup();
+
+    await contractErc20.tx.approve(contract.address, 1000);
+    await expect(
+      contract.tx.addLiquidity(1000, 0, { value: 10000 })
+    ).to.emit(contract, 'AddLiquidity');
+    const shares = await contract.query.balanceOf(sender.address);
+    expect(shares.output).to.equal(10000);
+
+    const reserves = await contract.query.reserves();
+    expect(reserves.output?.toHuman()).to.eql(['10,000', '1,000']);
+
+    // Buy tokens with native balance.
+    const tokenOut = await contract.query.getAmountOut(1000, true);
+    await expect(
+      contract.tx.swapExactIn(0, 1, { value: 1000 })
+    ).to.emit(contract, 'Swap');
+    const balance = await contractErc20.query.balanceOf(sender.address);
+    expect(balance.output).to.equal(999000 + tokenOut.output?.toJSON()['ok']);
+
+    // Sell tokens for native balance.
+    await contractErc20.tx.approve(contract.address, 10);
+    await expect(contract.tx.swapExactIn(10, 1)).to.emit(contract, 'Swap');
+  });
+
+  it('instantiates its token', async () => {
+    const signers = await getSigners();
+    const sender = signers[0];
+    const erc20Factory = await getContractFactory('erc20', sender);
+    await erc20Factory.putCode();
+    const codeHash = erc20Factory.abi.project.source.wasmHash.toHex();
+
+    const contractFactory = await getContractFactory('erc20swap', sender);
+    const contract = await contractFactory.deploy(
+      'newWithToken',
+      codeHash,
+      '1000000',
+      '0x01',
+      { value: '10000000000' }
+    );
+
+    const token = await contract.query.token();
+    const balance = await contract.query.tokenBalanceOf(sender.address);
+    expect(token.output?.toString()).to.not.equal(contract.address.toString());
+    expect(balance.output?.toJSON()).to.eql({ ok: 1000000 });
   });
 });
@@ -72,7 +72,7 @@ pub mod erc20owner {
 
     impl Erc20Ownable {
         /// Creates a new ERC-20 contract with the specified initial supply.
-        #[ink(constructor)]
+        #[ink(constructor, selector = "0x9BAE9D5E")]
         pub fn new(initial_supply: Balance) -> Self {
             let caller = Self::env().caller();
             let mut balances = StorageHashMap::new();
@@ -91,7 +91,7 @@ pub mod erc20owner {
         }
 
         /// Returns the total token supply.
-        #[ink(message)]
+        #[ink(message, selector = "0xDB6375A8")]
         pub fn total_supply(&self) -> Balance {
             *self.total_supply
         }
@@ -99,7 +99,7 @@ pub mod erc20owner {
         /// Returns the account balance for the specified `owner`.
         ///
         /// Returns `0` if the account is non-existent.
-        #[ink(message)]
+        #[ink(message, selector = "0x0F755A56")]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
             self.balances.get(&owner).copied().unwrap_or(0)
         }
@@ -107,7 +107,7 @@ pub mod erc20owner {
         /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
         ///
         /// Returns `0` if no allowance has been set `0`.
-        #[ink(message)]
+        #[ink(message, selector = "0x6A00165E")]
         pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
             self.allowances.get(&(owner, spender)).copied().unwrap_or(0)
         }
@@ -120,7 +120,7 @@ pub mod erc20owner {
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
-        #[ink(message)]
+        #[ink(message, selector = "0x84A15DA1")]
         pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
             let from = self.env().caller();
             self.transfer_from_to(from, to, value)
@@ -132,7 +132,7 @@ pub mod erc20owner {
         /// If this function is called again it overwrites the current allowance with `value`.
         ///
         /// An `Approval` event is emitted.
-        #[ink(message)]
+        #[ink(message, selector = "0x681266A0")]
         pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
             let owner = self.env().caller();
             self.allowances.insert((owner, spender), value);
@@ -158,7 +158,7 @@ pub mod erc20owner {
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the the account balance of `from`.
-        #[ink(message)]
+        #[ink(message, selector = "0x0B396F18")]
         pub fn transfer_from(
             &mut self,
             from: AccountId,
@@ -210,79 +210,44 @@ pub mod erc20owner {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
-        use ink_env::{
-            hash::{
-                Blake2x256,
-                CryptoHash,
-                HashOutput,
-            },
-            Clear,
+        use ::contract_test::{
+            as_caller,
+            assert_event,
+            default_accounts,
+            DefaultEnv,
         };
 
         type Event = <Erc20Ownable as ::ink_lang::BaseEvent>::Type;
 
         use ink_lang as ink;
 
+        ::contract_test::event_topics!(Transfer, "Erc20Ownable::Transfer", [from, to]);
+        ::contract_test::event_topics!(Approval, "Erc20Ownable::Approval", [owner, spender]);
+        ::contract_test::erc20_contract!(Erc20Ownable, Error);
+
         fn assert_transfer_event(
             event: &ink_env::test::EmittedEvent,
-            expected_from: Option<AccountId>,
-            expected_to: Option<AccountId>,
-            expected_value: Balance,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
         ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
-            if let Event::Transfer(Transfer { from, to, value }) = decoded_event {
-                assert_eq!(from, expected_from, "encountered invalid Transfer.from");
-                assert_eq!(to, expected_to, "encountered invalid Transfer.to");
-                assert_eq!(value, expected_value, "encountered invalid Trasfer.value");
-            } else {
-                panic!("encountered unexpected event kind: expected a Transfer event")
-            }
-            fn encoded_into_hash<T>(entity: &T) -> Hash
-            where
-                T: scale::Encode,
-            {
-                let mut result = Hash::clear();
-                let len_result = result.as_ref().len();
-                let encoded = entity.encode();
-                let len_encoded = encoded.len();
-                if len_encoded <= len_result {
-                    result.as_mut()[..len_encoded].copy_from_slice(&encoded);
-                    return result
-                }
-                let mut hash_output =
-                    <<Blake2x256 as HashOutput>::Type as Default>::default();
-                <Blake2x256 as CryptoHash>::hash(&encoded, &mut hash_output);
-                let copy_len = core::cmp::min(hash_output.len(), len_result);
-                result.as_mut()[0..copy_len].copy_from_slice(&hash_output[0..copy_len]);
-                result
-            }
-            let expected_topics = vec![
-                encoded_into_hash(&PrefixedValue {
-                    value: b"Erc20Ownable::Transfer",
-                    prefix: b"",
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20Ownable::Transfer::from",
-                    value: &expected_from,
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20Ownable::Transfer::to",
-                    value: &expected_to,
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20Ownable::Transfer::value",
-                    value: &expected_value,
-                }),
-            ];
-            for (n, (actual_topic, expected_topic)) in
-                event.topics.iter().zip(expected_topics).enumerate()
-            {
-                let topic = actual_topic
-                    .decode::<Hash>()
-                    .expect("encountered invalid topic encoding");
-                assert_eq!(topic, expected_topic, "encountered invalid topic at {}", n);
-            }
+            assert_event::<Event, _>(event, Transfer { from, to, value });
+        }
+
+        fn assert_approval_event(
+            event: &ink_env::test::EmittedEvent,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) {
+            assert_event::<Event, _>(
+                event,
+                Approval {
+                    owner,
+                    spender,
+                    value,
+                },
+            );
         }
 
         /// The default constructor does its job.
@@ -333,9 +298,7 @@ pub mod erc20owner {
                 Some(AccountId::from([0x01; 32])),
                 100,
             );
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
             // Alice owns all the tokens on deployment
             assert_eq!(erc20.balance_of(accounts.alice), 100);
             // Bob does not owns tokens
@@ -347,9 +310,7 @@ pub mod erc20owner {
             // Constructor works.
             let mut erc20 = Erc20Ownable::new(100);
             // Transfer event triggered during initial construction.
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
             // Alice transfers 10 tokens to Bob.
@@ -379,30 +340,12 @@ pub mod erc20owner {
         fn invalid_transfer_should_fail() {
             // Constructor works.
             let mut erc20 = Erc20Ownable::new(100);
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
-
             // Bob fails to transfers 10 tokens to Eve.
             assert_eq!(
-                erc20.transfer(accounts.eve, 10),
+                as_caller(accounts.bob, || erc20.transfer(accounts.eve, 10)),
                 Err(Error::InsufficientBalance)
             );
             // Alice owns all the tokens.
@@ -426,9 +369,7 @@ pub mod erc20owner {
             // Constructor works.
             let mut erc20 = Erc20Ownable::new(100);
             // Transfer event triggered during initial construction.
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
 
             // Bob fails to transfer tokens owned by Alice.
             assert_eq!(
@@ -441,25 +382,11 @@ pub mod erc20owner {
             // The approve event takes place.
             assert_eq!(ink_env::test::recorded_events().count(), 2);
 
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call.
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller.
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
-
             // Bob transfers tokens from Alice to Eve.
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, 10),
+                as_caller(accounts.bob, || {
+                    erc20.transfer_from(accounts.alice, accounts.eve, 10)
+                }),
                 Ok(())
             );
             // Eve owns tokens.
@@ -474,7 +401,12 @@ pub mod erc20owner {
                 Some(AccountId::from([0x01; 32])),
                 100,
             );
-            // The second event `emitted_events[1]` is an Approve event that we skip checking.
+            assert_approval_event(
+                &emitted_events[1],
+                AccountId::from([0x01; 32]),
+                AccountId::from([0x02; 32]),
+                10,
+            );
             assert_transfer_event(
                 &emitted_events[2],
                 Some(AccountId::from([0x01; 32])),
@@ -486,36 +418,20 @@ pub mod erc20owner {
         #[ink::test]
         fn allowance_must_not_change_on_failed_transfer() {
             let mut erc20 = Erc20Ownable::new(100);
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
 
             // Alice approves Bob for token transfers on her behalf.
             let alice_balance = erc20.balance_of(accounts.alice);
             let initial_allowance = alice_balance + 2;
             assert_eq!(erc20.approve(accounts.bob, initial_allowance), Ok(()));
 
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call.
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller.
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
-
             // Bob tries to transfer tokens from Alice to Eve.
             let emitted_events_before =
                 ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1),
+                as_caller(accounts.bob, || {
+                    erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1)
+                }),
                 Err(Error::InsufficientBalance)
             );
             // Allowance must have stayed the same
@@ -528,27 +444,20 @@ pub mod erc20owner {
                 ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(emitted_events_before.len(), emitted_events_after.len());
         }
-    }
 
-    /// For calculating the event topic hash.
-    struct PrefixedValue<'a, 'b, T> {
-        pub prefix: &'a [u8],
-        pub value: &'b T,
-    }
+        proptest::proptest! {
+            #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
 
-    impl<X> scale::Encode for PrefixedValue<'_, '_, X>
-    where
-        X: scale::Encode,
-    {
-        #[inline]
-        fn size_hint(&self) -> usize {
-            self.prefix.size_hint() + self.value.size_hint()
+            /// Random calls keep the balances, allowances and events consistent.
+            #[test]
+            fn erc20_invariants_hold(ops in ::contract_test::erc20_ops(32)) {
+                ::contract_test::check_erc20_ops::<Erc20Ownable>(&ops);
+            }
         }
 
-        #[inline]
-        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
-            self.prefix.encode_to(dest);
-            self.value.encode_to(dest);
+        #[test]
+        fn message_selectors_are_stable() {
+            ::contract_test::assert_message_selectors!(::contract_test::ERC20_MESSAGES);
         }
     }
 }
@@ -3,7 +3,7 @@
 use ink_lang as ink;
 
 #[ink::contract]
-mod owner {
+pub mod owner {
     #[cfg(not(feature = "ink-as-dependency"))]
     use ink_storage::lazy::Lazy;
 
@@ -61,7 +61,7 @@ mod owner {
         /// `ensure_owner` functions anymore. Can only be called by the current owner.
         /// NOTE: Renouncing ownership will leave the contract without an owner,
         /// thereby removing any functionality that is only available to the owner.
-        #[ink(message)]
+        #[ink(message, selector = "0x8C90065B")]
         pub fn renounce_ownership(&mut self) {
             self.ensure_caller_is_owner();
 
@@ -75,7 +75,7 @@ mod owner {
 
         /// Transfers ownership of the contract to a new account (`newOwner`).
         /// Can only be called by the current owner.
-        #[ink(message)]
+        #[ink(message, selector = "0x107E33EA")]
         pub fn transfer_ownership(&mut self, new_owner: AccountId) {
             self.ensure_caller_is_owner();
 
@@ -88,7 +88,7 @@ mod owner {
         }
 
         /// Get Contract 's Owner
-        #[ink(message)]
+        #[ink(message, selector = "0x07FCD0B1")]
         pub fn get_owner(&self) -> Option<AccountId> {
             *self.get_ownership()
         }
@@ -125,12 +125,38 @@ mod owner {
         /// Imports `ink_lang` so we can use `#[ink::test]`.
         use ink_lang as ink;
 
+        use ::contract_test::{
+            assert_event,
+            default_accounts,
+            DefaultEnv,
+        };
+
+        type Event = <Owner as ::ink_lang::BaseEvent>::Type;
+
+        ::contract_test::event_topics!(
+            OwnershipTransferred,
+            "Owner::OwnershipTransferred",
+            [previous_owner, new_owner]
+        );
+
+        fn assert_ownership_transferred_event(
+            event: &ink_env::test::EmittedEvent,
+            previous_owner: Option<AccountId>,
+            new_owner: Option<AccountId>,
+        ) {
+            assert_event::<Event, _>(
+                event,
+                OwnershipTransferred {
+                    previous_owner,
+                    new_owner,
+                },
+            );
+        }
+
         /// We test a simple use case of our contract.
         #[ink::test]
         fn it_works() {
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<DefaultEnv>();
 
             let mut owner_test = Owner::new();
             let default_owner = AccountId::from([0x01; 32]);
@@ -148,5 +174,40 @@ mod owner {
 
             assert_eq!(owner_test.get_owner(), None);
         }
+
+        #[ink::test]
+        fn ownership_events_work() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut owner_test = Owner::new();
+            owner_test.transfer_ownership(accounts.alice);
+            owner_test.renounce_ownership();
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_ownership_transferred_event(
+                &emitted_events[0],
+                None,
+                Some(accounts.alice),
+            );
+            assert_ownership_transferred_event(
+                &emitted_events[1],
+                Some(accounts.alice),
+                Some(accounts.alice),
+            );
+            assert_ownership_transferred_event(
+                &emitted_events[2],
+                Some(accounts.alice),
+                None,
+            );
+        }
+
+        #[test]
+        fn message_selectors_are_stable() {
+            ::contract_test::assert_message_selectors!([
+                ("renounce_ownership", ::owner_basic::selectors::RENOUNCE_OWNERSHIP),
+                ("transfer_ownership", ::owner_basic::selectors::TRANSFER_OWNERSHIP),
+                ("get_owner", ::owner_basic::selectors::GET_OWNER),
+            ]);
+        }
     }
 }
@@ -0,0 +1,102 @@
+pub mod call {
+    use ::ink_env::Environment;
+
+    /// Calls `selector` on the contract `callee` with `args`, transferring `value`,
+    /// and returns its decoded reply.
+    #[cfg(not(any(test, feature = "test-utils")))]
+    pub fn invoke<T, Args, R>(
+        callee: &T::AccountId,
+        selector: [u8; 4],
+        args: Args,
+        value: T::Balance,
+    ) -> Result<R, ::ink_env::Error>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        use ::ink_env::call::{
+            build_call,
+            utils::ReturnType,
+            ExecutionInput,
+            Selector,
+        };
+
+        build_call::<T>()
+            .callee(callee.clone())
+            .gas_limit(0)
+            .transferred_value(value)
+            .exec_input(ExecutionInput::new(Selector::new(selector)).push_arg(args))
+            .returns::<ReturnType<R>>()
+            .fire()
+    }
+
+    /// The off-chain environment cannot dispatch cross-contract calls, so tests
+    /// route the calls of `invoke` to the contracts they host, with the
+    /// `test-utils` feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub mod test {
+        use ::ink_env::Error;
+        use std::{
+            cell::RefCell,
+            vec::Vec,
+        };
+
+        type Router = Box<dyn Fn(&[u8], &[u8], Vec<u8>) -> Result<Vec<u8>, Error>>;
+
+        std::thread_local! {
+            static ROUTER: RefCell<Option<Router>> = RefCell::new(None);
+        }
+
+        /// Routes the calls of `invoke` made on this thread to `router`.
+        ///
+        /// The router gets the encoded callee, the encoded transferred value and the
+        /// input, selector first, and returns the encoded reply. Without a router the
+        /// calls fail with `NotCallable`.
+        pub fn set_router<F>(router: F)
+        where
+            F: Fn(&[u8], &[u8], Vec<u8>) -> Result<Vec<u8>, Error> + 'static,
+        {
+            ROUTER.with(|cell| *cell.borrow_mut() = Some(Box::new(router)));
+        }
+
+        pub(crate) fn route(
+            callee: &[u8],
+            value: &[u8],
+            input: Vec<u8>,
+        ) -> Result<Vec<u8>, Error> {
+            ROUTER.with(|cell| {
+                match &*cell.borrow() {
+                    Some(router) => router(callee, value, input),
+                    None => Err(Error::NotCallable),
+                }
+            })
+        }
+    }
+
+    /// Routes the call to the router of `test::set_router`.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn invoke<T, Args, R>(
+        callee: &T::AccountId,
+        selector: [u8; 4],
+        args: Args,
+        value: T::Balance,
+    ) -> Result<R, ::ink_env::Error>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        use scale::Encode;
+
+        let mut input = selector.to_vec();
+        args.encode_to(&mut input);
+        let reply = test::route(&callee.encode(), &value.encode(), input)?;
+        R::decode(&mut &reply[..]).map_err(::ink_env::Error::Decode)
+    }
+}
+
+pub use call::invoke;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use call::test::set_router;
@@ -0,0 +1,117 @@
+pub mod factory {
+    use ::ink_env::{
+        call::FromAccountId,
+        Environment,
+    };
+
+    /// Instantiates a contract from the uploaded `code_hash`, calling the constructor
+    /// `selector` with `args`.
+    ///
+    /// `endowment` is transferred to the new contract and `salt` tells apart contracts
+    /// of the same code and arguments. Returns a reference `R` to the new contract.
+    #[cfg(not(any(test, feature = "test-utils")))]
+    pub fn instantiate<T, Args, R>(
+        code_hash: T::Hash,
+        selector: [u8; 4],
+        args: Args,
+        endowment: T::Balance,
+        salt: &[u8],
+    ) -> Result<R, ::ink_env::Error>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: FromAccountId<T>,
+    {
+        use ::ink_env::call::{
+            build_create,
+            ExecutionInput,
+            Selector,
+        };
+
+        build_create::<T, R>()
+            .code_hash(code_hash)
+            .gas_limit(0)
+            .endowment(endowment)
+            .exec_input(ExecutionInput::new(Selector::new(selector)).push_arg(args))
+            .salt_bytes(salt)
+            .params()
+            .instantiate()
+    }
+
+    /// The off-chain environment cannot instantiate contracts, so tests answer the
+    /// calls of `instantiate` themselves, with the `test-utils` feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub mod test {
+        use ::ink_env::Error;
+        use std::{
+            cell::RefCell,
+            vec::Vec,
+        };
+
+        type Instantiator =
+            Box<dyn Fn(&[u8], &[u8], Vec<u8>, &[u8]) -> Result<Vec<u8>, Error>>;
+
+        std::thread_local! {
+            static INSTANTIATOR: RefCell<Option<Instantiator>> = RefCell::new(None);
+        }
+
+        /// Answers the calls of `instantiate` made on this thread with `instantiator`.
+        ///
+        /// The instantiator gets the encoded code hash, the encoded endowment, the
+        /// input, selector first, and the salt, and returns the encoded account of the
+        /// new contract. Without an instantiator the calls fail with `CodeNotFound`.
+        pub fn set_instantiator<F>(instantiator: F)
+        where
+            F: Fn(&[u8], &[u8], Vec<u8>, &[u8]) -> Result<Vec<u8>, Error> + 'static,
+        {
+            INSTANTIATOR.with(|cell| *cell.borrow_mut() = Some(Box::new(instantiator)));
+        }
+
+        pub(crate) fn create(
+            code_hash: &[u8],
+            endowment: &[u8],
+            input: Vec<u8>,
+            salt: &[u8],
+        ) -> Result<Vec<u8>, Error> {
+            INSTANTIATOR.with(|cell| {
+                match &*cell.borrow() {
+                    Some(instantiator) => instantiator(code_hash, endowment, input, salt),
+                    None => Err(Error::CodeNotFound),
+                }
+            })
+        }
+    }
+
+    /// Answers the call with the instantiator of `test::set_instantiator`.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn instantiate<T, Args, R>(
+        code_hash: T::Hash,
+        selector: [u8; 4],
+        args: Args,
+        endowment: T::Balance,
+        salt: &[u8],
+    ) -> Result<R, ::ink_env::Error>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: FromAccountId<T>,
+    {
+        use scale::{
+            Decode,
+            Encode,
+        };
+
+        let mut input = selector.to_vec();
+        args.encode_to(&mut input);
+        let account =
+            test::create(&code_hash.encode(), &endowment.encode(), input, salt)?;
+        let account_id = T::AccountId::decode(&mut &account[..])
+            .map_err(::ink_env::Error::Decode)?;
+        Ok(R::from_account_id(account_id))
+    }
+}
+
+pub use factory::instantiate;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use factory::test::set_instantiator;
@@ -2,6 +2,13 @@
 
 mod traits;
 mod modules;
+mod call;
+mod factory;
+mod migrate;
+#[cfg(feature = "test-utils")]
+mod mock;
+mod namespace;
+mod receiver;
 
 pub use traits::{
     AccountId,
@@ -35,3 +42,44 @@ pub use contract::{
 pub use modules::{
     Module,
 };
+
+pub use call::invoke;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use call::set_router;
+
+pub use factory::instantiate;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use factory::set_instantiator;
+
+pub use migrate::{
+    Migrate,
+    MigrateError,
+};
+
+pub use namespace::{
+    Namespace,
+    Namespaced,
+};
+
+pub use receiver::{
+    accepts,
+    call_receiver,
+    ReceiverAccess,
+    ON_ERC1155_BATCH_RECEIVED,
+    ON_ERC1155_RECEIVED,
+    ON_ERC20_RECEIVED,
+    ON_ERC721_RECEIVED,
+    RECEIVER_GAS_LIMIT,
+};
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use receiver::set_receiver;
+
+#[cfg(feature = "test-utils")]
+pub use mock::{
+    MockContract,
+    MockEnv,
+    MockEvent,
+};
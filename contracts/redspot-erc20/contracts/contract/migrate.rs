@@ -0,0 +1,175 @@
+pub mod migrate {
+    use crate::Namespace;
+    use ::ink_env::hash::Blake2x256;
+    use ::ink_primitives::Key;
+
+    /// The migration error types.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MigrateError {
+        /// Returned if the stored data has a newer version than the code.
+        UnknownVersion(u32),
+    }
+
+    /// Module data with a versioned layout, migrated from the layouts of older
+    /// versions.
+    ///
+    /// The version is stored beside the data at `blake2(NAMESPACE ++ "::version")`,
+    /// so it does not move when the layout changes. Data without a stored version
+    /// has version 1.
+    pub trait Migrate: Namespace {
+        /// The version of the current layout.
+        const VERSION: u32;
+
+        /// Migrates the data from the layout of version `from` to `from + 1`.
+        ///
+        /// Fields added by the next version are not in the storage yet, they must be
+        /// set without being read.
+        fn migrate_from(&mut self, from: u32);
+
+        /// Returns the key of the stored version.
+        fn version_key() -> Key {
+            let mut output = [0x00; 32];
+            ::ink_env::hash_encoded::<Blake2x256, _>(
+                &(Self::NAMESPACE, b"::version"),
+                &mut output,
+            );
+            Key::from(output)
+        }
+
+        /// Returns the version of the stored data.
+        fn stored_version() -> u32 {
+            ::ink_env::get_contract_storage::<u32>(&Self::version_key())
+                .ok()
+                .flatten()
+                .unwrap_or(1)
+        }
+
+        /// Stores the current version.
+        ///
+        /// Called once by the constructor of the contract holding the data, through
+        /// `Namespaced::init`, not by the constructor of the data.
+        fn init() {
+            ::ink_env::set_contract_storage(&Self::version_key(), &Self::VERSION);
+        }
+
+        /// Migrates the data version by version up to `VERSION`.
+        ///
+        /// Returns the version the data had before.
+        fn migrate(&mut self) -> Result<u32, MigrateError> {
+            let from = Self::stored_version();
+            if from > Self::VERSION {
+                return Err(MigrateError::UnknownVersion(from))
+            }
+            for version in from..Self::VERSION {
+                self.migrate_from(version);
+            }
+            Self::init();
+            Ok(from)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ::ink_primitives::KeyPtr;
+        use ::ink_storage::{
+            lazy::Lazy,
+            traits::SpreadLayout,
+        };
+
+        #[derive(Debug, SpreadLayout)]
+        struct DataV1 {
+            total_supply: Lazy<u128>,
+        }
+
+        impl Namespace for DataV1 {
+            const NAMESPACE: &'static [u8] = b"test::Data";
+        }
+
+        #[derive(Debug, SpreadLayout)]
+        struct DataV2 {
+            total_supply: Lazy<u128>,
+            holders: Lazy<u32>,
+        }
+
+        impl Namespace for DataV2 {
+            const NAMESPACE: &'static [u8] = b"test::Data";
+        }
+
+        impl Migrate for DataV2 {
+            const VERSION: u32 = 2;
+
+            fn migrate_from(&mut self, from: u32) {
+                if from == 1 {
+                    Lazy::set(&mut self.holders, 0);
+                }
+            }
+        }
+
+        fn root_key_ptr() -> KeyPtr {
+            KeyPtr::from(<DataV2 as Namespace>::root_key())
+        }
+
+        #[test]
+        fn migrate_v1_to_v2_works() {
+            ::ink_env::test::run_test::<::ink_env::DefaultEnvironment, _>(|_| {
+                // A contract deployed with the v1 layout, without stored version.
+                let v1 = DataV1 {
+                    total_supply: Lazy::new(100),
+                };
+                SpreadLayout::push_spread(&v1, &mut root_key_ptr());
+
+                let mut v2 = <DataV2 as SpreadLayout>::pull_spread(&mut root_key_ptr());
+                assert_eq!(DataV2::stored_version(), 1);
+                assert_eq!(v2.migrate(), Ok(1));
+                SpreadLayout::push_spread(&v2, &mut root_key_ptr());
+
+                let v2 = <DataV2 as SpreadLayout>::pull_spread(&mut root_key_ptr());
+                assert_eq!(*v2.total_supply, 100);
+                assert_eq!(*v2.holders, 0);
+                assert_eq!(DataV2::stored_version(), 2);
+                Ok(())
+            })
+            .unwrap()
+        }
+
+        #[test]
+        fn namespaced_init_stores_version() {
+            ::ink_env::test::run_test::<::ink_env::DefaultEnvironment, _>(|_| {
+                let data = DataV2 {
+                    total_supply: Lazy::new(100),
+                    holders: Lazy::new(0),
+                };
+                assert_eq!(DataV2::stored_version(), 1);
+                let _data = crate::Namespaced::init(data);
+                assert_eq!(DataV2::stored_version(), 2);
+                Ok(())
+            })
+            .unwrap()
+        }
+
+        #[test]
+        fn migrate_is_idempotent() {
+            ::ink_env::test::run_test::<::ink_env::DefaultEnvironment, _>(|_| {
+                let mut v2 = DataV2 {
+                    total_supply: Lazy::new(100),
+                    holders: Lazy::new(3),
+                };
+                DataV2::init();
+                assert_eq!(v2.migrate(), Ok(2));
+                assert_eq!(*v2.holders, 3);
+
+                ::ink_env::set_contract_storage(&DataV2::version_key(), &3u32);
+                assert_eq!(v2.migrate(), Err(MigrateError::UnknownVersion(3)));
+                Ok(())
+            })
+            .unwrap()
+        }
+    }
+}
+
+pub use migrate::{
+    Migrate,
+    MigrateError,
+};
@@ -0,0 +1,174 @@
+pub mod mock {
+    use crate::{
+        contract::{
+            Env,
+            EnvAccess,
+        },
+        receiver::receiver::call_receiver,
+        Module,
+        ReceiverAccess,
+    };
+    use ::ink_env::{
+        DefaultEnvironment,
+        Environment,
+    };
+    use std::{
+        cell::RefCell,
+        vec::Vec,
+    };
+
+    pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    pub type Balance = <DefaultEnvironment as Environment>::Balance;
+    pub type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+
+    /// An event recorded by a mock contract, with the encoded arguments of its emit
+    /// method.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct MockEvent {
+        pub name: &'static str,
+        pub data: Vec<u8>,
+    }
+
+    impl MockEvent {
+        pub fn new<T: scale::Encode>(name: &'static str, args: T) -> Self {
+            Self {
+                name,
+                data: args.encode(),
+            }
+        }
+    }
+
+    struct State {
+        caller: AccountId,
+        transferred_balance: Balance,
+        block_number: BlockNumber,
+        events: Vec<MockEvent>,
+    }
+
+    impl Default for State {
+        fn default() -> Self {
+            Self {
+                caller: AccountId::from([0x01; 32]),
+                transferred_balance: 0,
+                block_number: 0,
+                events: Vec::new(),
+            }
+        }
+    }
+
+    std::thread_local! {
+        static STATE: RefCell<State> = RefCell::new(State::default());
+    }
+
+    /// An environment for testing modules as plain Rust, without an ink! contract.
+    ///
+    /// The caller, the transferred balance, the block number and the emitted events
+    /// are thread-local, and the caller defaults to `[0x01; 32]`.
+    pub enum MockEnv {}
+
+    impl Env for MockEnv {
+        type AccountId = AccountId;
+        type Balance = Balance;
+        type Hash = <DefaultEnvironment as Environment>::Hash;
+        type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+        type BlockNumber = BlockNumber;
+    }
+
+    impl EnvAccess<MockEnv> for MockEnv {
+        fn caller() -> AccountId {
+            STATE.with(|state| state.borrow().caller)
+        }
+
+        fn transferred_balance() -> Balance {
+            STATE.with(|state| state.borrow().transferred_balance)
+        }
+    }
+
+    impl MockEnv {
+        /// Resets the state and runs `f`.
+        ///
+        /// The module data of a mock contract is never pushed to the storage, so `f`
+        /// does not need the off-chain environment of ink!.
+        pub fn run(f: impl FnOnce()) {
+            STATE.with(|state| *state.borrow_mut() = State::default());
+            f()
+        }
+
+        pub fn set_caller(caller: AccountId) {
+            STATE.with(|state| state.borrow_mut().caller = caller);
+        }
+
+        pub fn set_transferred_balance(value: Balance) {
+            STATE.with(|state| state.borrow_mut().transferred_balance = value);
+        }
+
+        pub fn block_number() -> BlockNumber {
+            STATE.with(|state| state.borrow().block_number)
+        }
+
+        pub fn advance_blocks(n: BlockNumber) {
+            STATE.with(|state| state.borrow_mut().block_number += n);
+        }
+
+        /// Records an event, called by the emit traits of the mock contracts.
+        pub fn record_event(event: MockEvent) {
+            STATE.with(|state| state.borrow_mut().events.push(event));
+        }
+
+        /// Returns the events recorded since the start of `run`.
+        pub fn events() -> Vec<MockEvent> {
+            STATE.with(|state| state.borrow().events.clone())
+        }
+    }
+
+    /// A contract holding the module data `T`, with the environment `MockEnv`.
+    ///
+    /// Module crates implement their emit traits for it behind their own
+    /// `test-utils` feature, recording the events with `MockEnv::record_event`.
+    #[derive(Debug)]
+    pub struct MockContract<T> {
+        pub data: T,
+    }
+
+    impl<T> MockContract<T> {
+        pub fn new(data: T) -> Self {
+            Self { data }
+        }
+    }
+
+    impl<T> Module<T> for MockContract<T> {
+        fn get_module(&self) -> &T {
+            &self.data
+        }
+
+        fn get_module_mut(&mut self) -> &mut T {
+            &mut self.data
+        }
+    }
+
+    impl<T> EnvAccess<MockEnv> for MockContract<T> {
+        fn caller() -> AccountId {
+            MockEnv::caller()
+        }
+
+        fn transferred_balance() -> Balance {
+            MockEnv::transferred_balance()
+        }
+    }
+
+    impl<T> ReceiverAccess<MockEnv> for MockContract<T> {
+        fn call_receiver<Args: scale::Encode>(
+            to: &AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> Result<[u8; 4], ::ink_env::Error> {
+            call_receiver::<DefaultEnvironment, Args>(to, selector, args)
+        }
+    }
+}
+
+pub use mock::{
+    MockContract,
+    MockEnv,
+    MockEvent,
+};
@@ -0,0 +1,103 @@
+pub mod namespace {
+    use crate::Migrate;
+    use core::ops::{
+        Deref,
+        DerefMut,
+    };
+    use ::ink_env::hash::{
+        Blake2x256,
+        CryptoHash,
+    };
+    use ::ink_primitives::{
+        Key,
+        KeyPtr,
+    };
+    use ::ink_storage::traits::SpreadLayout;
+
+    /// The storage namespace of a module's data.
+    pub trait Namespace {
+        /// The name of the namespace, e.g. `b"erc20_basic::Data"`, it must be unique
+        /// between the modules of a contract and never change.
+        const NAMESPACE: &'static [u8];
+
+        /// Returns the root key of the namespace, `blake2(NAMESPACE)`.
+        fn root_key() -> Key {
+            let mut output = [0x00; 32];
+            <Blake2x256 as CryptoHash>::hash(Self::NAMESPACE, &mut output);
+            Key::from(output)
+        }
+    }
+
+    /// Module data stored from the root key of its namespace.
+    ///
+    /// The data takes no space in the storage struct containing it, so the keys of
+    /// a module stay the same when fields are reordered or modules are added.
+    #[derive(Debug)]
+    pub struct Namespaced<T: Namespace> {
+        data: T,
+    }
+
+    impl<T: Namespace> Namespaced<T> {
+        pub fn new(data: T) -> Self {
+            Self { data }
+        }
+    }
+
+    impl<T: Migrate> Namespaced<T> {
+        /// Wraps the data of a new contract and stores the version of its layout,
+        /// contract constructors use this instead of `new`.
+        pub fn init(data: T) -> Self {
+            T::init();
+            Self::new(data)
+        }
+    }
+
+    impl<T: Namespace> Deref for Namespaced<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.data
+        }
+    }
+
+    impl<T: Namespace> DerefMut for Namespaced<T> {
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.data
+        }
+    }
+
+    impl<T: Namespace + SpreadLayout> SpreadLayout for Namespaced<T> {
+        const FOOTPRINT: u64 = 0;
+        const REQUIRES_DEEP_CLEAN_UP: bool = T::REQUIRES_DEEP_CLEAN_UP;
+
+        fn pull_spread(_ptr: &mut KeyPtr) -> Self {
+            let mut ptr = KeyPtr::from(T::root_key());
+            Self::new(T::pull_spread(&mut ptr))
+        }
+
+        fn push_spread(&self, _ptr: &mut KeyPtr) {
+            let mut ptr = KeyPtr::from(T::root_key());
+            self.data.push_spread(&mut ptr);
+        }
+
+        fn clear_spread(&self, _ptr: &mut KeyPtr) {
+            let mut ptr = KeyPtr::from(T::root_key());
+            self.data.clear_spread(&mut ptr);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<T> ::ink_storage::traits::StorageLayout for Namespaced<T>
+    where
+        T: Namespace + ::ink_storage::traits::StorageLayout,
+    {
+        fn layout(_key_ptr: &mut KeyPtr) -> ::ink_metadata::layout2::Layout {
+            T::layout(&mut KeyPtr::from(T::root_key()))
+        }
+    }
+}
+
+pub use namespace::{
+    Namespace,
+    Namespaced,
+};
@@ -0,0 +1,180 @@
+pub mod receiver {
+    use crate::contract::{
+        Env,
+        EnvAccess,
+    };
+
+    /// Selector of `on_erc20_received(operator, from, value, data)`, the ERC-1363
+    /// `onTransferReceived` selector.
+    pub const ON_ERC20_RECEIVED: [u8; 4] = [0x88, 0xa7, 0xca, 0x5c];
+    /// Selector of `on_erc721_received(operator, from, id, data)`.
+    pub const ON_ERC721_RECEIVED: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+    /// Selector of `on_erc1155_received(operator, from, id, value, data)`.
+    pub const ON_ERC1155_RECEIVED: [u8; 4] = [0xf2, 0x3a, 0x6e, 0x61];
+    /// Selector of `on_erc1155_batch_received(operator, from, ids, values, data)`.
+    pub const ON_ERC1155_BATCH_RECEIVED: [u8; 4] = [0xbc, 0x19, 0x7c, 0x81];
+
+    /// Gas limit of a receiver callback.
+    ///
+    /// The callback runs code picked by the recipient, so it is bounded to keep it
+    /// from spending the gas left for the rest of the transfer.
+    pub const RECEIVER_GAS_LIMIT: u64 = 10_000_000_000;
+
+    /// Access to the recipient of a safe transfer.
+    ///
+    /// A receiver contract accepts tokens by replying to the callback with its
+    /// selector as magic value.
+    pub trait ReceiverAccess<E: Env>: EnvAccess<E> {
+        /// Calls `selector` on `to` with `args`, returns its reply or the error of the
+        /// call.
+        fn call_receiver<Args: scale::Encode>(
+            to: &E::AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> Result<[u8; 4], ::ink_env::Error>;
+
+        /// Returns `true` if `to` can take tokens: plain accounts always can,
+        /// contracts must reply `selector` to the callback.
+        fn check_on_received<Args: scale::Encode>(
+            to: &E::AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> bool {
+            accepts(selector, Self::call_receiver(to, selector, args))
+        }
+    }
+
+    /// Returns `true` if the `reply` to the callback `selector` accepts the tokens.
+    ///
+    /// ink! 3.0-rc3 has no host function to look up the code of an account, so the
+    /// callback itself tells them apart: only a plain account is `NotCallable`.
+    pub fn accepts(selector: [u8; 4], reply: Result<[u8; 4], ::ink_env::Error>) -> bool {
+        match reply {
+            Ok(reply) => reply == selector,
+            Err(::ink_env::Error::NotCallable) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Calls the receiver callback `selector` on `to`, with `RECEIVER_GAS_LIMIT`.
+    #[cfg(not(any(test, feature = "test-utils")))]
+    pub fn call_receiver<T: ::ink_env::Environment, Args: scale::Encode>(
+        to: &T::AccountId,
+        selector: [u8; 4],
+        args: Args,
+    ) -> Result<[u8; 4], ::ink_env::Error> {
+        use ::ink_env::call::{
+            build_call,
+            utils::ReturnType,
+            ExecutionInput,
+            Selector,
+        };
+
+        build_call::<T>()
+            .callee(to.clone())
+            .gas_limit(RECEIVER_GAS_LIMIT)
+            .exec_input(ExecutionInput::new(Selector::new(selector)).push_arg(args))
+            .returns::<ReturnType<[u8; 4]>>()
+            .fire()
+    }
+
+    /// The off-chain environment cannot dispatch cross-contract calls, so tests
+    /// register the receiver contracts and their replies here, with the
+    /// `test-utils` feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub mod test {
+        use scale::Encode;
+        use std::{
+            cell::RefCell,
+            vec::Vec,
+        };
+
+        type Receivers = Vec<(Vec<u8>, Option<[u8; 4]>)>;
+
+        std::thread_local! {
+            static RECEIVERS: RefCell<Receivers> = RefCell::new(Vec::new());
+        }
+
+        /// Marks `account` as a receiver contract replying `reply` to every callback,
+        /// `None` makes the callback fail.
+        pub fn set_receiver<A: scale::Encode>(account: &A, reply: Option<[u8; 4]>) {
+            let key = account.encode();
+            RECEIVERS.with(|receivers| {
+                let mut receivers = receivers.borrow_mut();
+                receivers.retain(|(registered, _)| registered != &key);
+                receivers.push((key, reply));
+            });
+        }
+
+        pub(crate) fn get_receiver<A: scale::Encode>(
+            account: &A,
+        ) -> Option<Option<[u8; 4]>> {
+            let key = account.encode();
+            RECEIVERS.with(|receivers| {
+                receivers
+                    .borrow()
+                    .iter()
+                    .find(|(registered, _)| registered == &key)
+                    .map(|(_, reply)| *reply)
+            })
+        }
+    }
+
+    /// Replies from the registry of `test::set_receiver`, the other accounts are
+    /// plain accounts.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn call_receiver<T: ::ink_env::Environment, Args: scale::Encode>(
+        to: &T::AccountId,
+        _selector: [u8; 4],
+        _args: Args,
+    ) -> Result<[u8; 4], ::ink_env::Error> {
+        match test::get_receiver(to) {
+            Some(Some(reply)) => Ok(reply),
+            Some(None) => Err(::ink_env::Error::CalleeTrapped),
+            None => Err(::ink_env::Error::NotCallable),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const SELECTOR: [u8; 4] = ON_ERC721_RECEIVED;
+
+        #[test]
+        fn plain_account_accepts() {
+            assert!(accepts(SELECTOR, Err(::ink_env::Error::NotCallable)));
+        }
+
+        #[test]
+        fn contract_accepts_with_selector() {
+            assert!(accepts(SELECTOR, Ok(SELECTOR)));
+        }
+
+        #[test]
+        fn contract_rejects_with_other_reply() {
+            assert!(!accepts(SELECTOR, Ok(ON_ERC20_RECEIVED)));
+            assert!(!accepts(SELECTOR, Ok([0x00; 4])));
+        }
+
+        #[test]
+        fn failed_callback_rejects() {
+            assert!(!accepts(SELECTOR, Err(::ink_env::Error::CalleeTrapped)));
+            assert!(!accepts(SELECTOR, Err(::ink_env::Error::CalleeReverted)));
+        }
+    }
+}
+
+pub use receiver::{
+    accepts,
+    call_receiver,
+    ReceiverAccess,
+    ON_ERC1155_BATCH_RECEIVED,
+    ON_ERC1155_RECEIVED,
+    ON_ERC20_RECEIVED,
+    ON_ERC721_RECEIVED,
+    RECEIVER_GAS_LIMIT,
+};
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use receiver::test::set_receiver;
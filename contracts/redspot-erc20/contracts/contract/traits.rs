@@ -18,6 +18,7 @@ pub mod traits {
     use num_traits::{
         checked_pow,
         Bounded,
+        CheckedAdd,
         CheckedMul,
         One,
         Unsigned,
@@ -53,6 +54,7 @@ pub mod traits {
         + MulAssign<Self>
         + Div<Self, Output = Self>
         + DivAssign<Self>
+        + CheckedAdd
         + CheckedMul
         + Saturating
         + TryFrom<u16>
@@ -84,6 +86,7 @@ pub mod traits {
             + MulAssign<Self>
             + Div<Self, Output = Self>
             + DivAssign<Self>
+            + CheckedAdd
             + CheckedMul
             + Saturating
             + TryFrom<u16>
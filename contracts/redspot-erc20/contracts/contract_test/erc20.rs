@@ -0,0 +1,442 @@
+//! Property tests of the ERC-20 contracts over random sequences of calls.
+//!
+//! The calls are checked against `Erc20State`, a model of the standard, so two
+//! contracts passing the same sequence behave the same.
+
+use crate::{
+    as_caller,
+    default_accounts,
+    AccountId,
+    Balance,
+    DefaultEnv,
+};
+use ::ink_env::DefaultEnvironment;
+use core::fmt::Debug;
+use proptest::{
+    collection::vec,
+    prelude::*,
+};
+
+/// The number of accounts the calls are made between, the default accounts.
+pub const ACCOUNTS: usize = 6;
+
+/// The initial supply of the token under test, minted to Alice.
+pub const INITIAL_SUPPLY: Balance = 1000;
+
+/// The messages of an ERC-20 contract, implemented by `erc20_contract!`.
+pub trait Erc20Contract {
+    /// The error returned by the messages, compared to the model by its name.
+    type Error: Debug;
+
+    fn new(initial_supply: Balance) -> Self;
+    fn total_supply(&self) -> Balance;
+    fn balance_of(&self, owner: AccountId) -> Balance;
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+    fn transfer(&mut self, to: AccountId, value: Balance) -> Result<(), Self::Error>;
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), Self::Error>;
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Implements `Erc20Contract` for a contract from its messages and error type.
+///
+/// Outside the crate of the contract the trait is implemented for a new wrapper
+/// around it, which dereferences to the contract.
+///
+/// ```ignore
+/// ::contract_test::erc20_contract!(Erc20, Error);
+/// ::contract_test::erc20_contract!(ModularErc20(::erc20::erc20::Erc20), Error);
+/// ```
+#[macro_export]
+macro_rules! erc20_contract {
+    ($wrapper:ident($contract:ty), $error:ty) => {
+        pub struct $wrapper(pub $contract);
+
+        impl ::core::ops::Deref for $wrapper {
+            type Target = $contract;
+
+            fn deref(&self) -> &$contract {
+                &self.0
+            }
+        }
+
+        impl ::core::ops::DerefMut for $wrapper {
+            fn deref_mut(&mut self) -> &mut $contract {
+                &mut self.0
+            }
+        }
+
+        $crate::erc20_contract!(@impl $wrapper, $contract, $error, [Self], [.0]);
+    };
+    ($contract:ident, $error:ty) => {
+        $crate::erc20_contract!(@impl $contract, $contract, $error, [], []);
+    };
+    (@impl $self_ty:ty, $contract:ty, $error:ty, [$($wrap:tt)*], [$($field:tt)*]) => {
+        impl $crate::Erc20Contract for $self_ty {
+            type Error = $error;
+
+            fn new(initial_supply: $crate::Balance) -> Self {
+                $($wrap)*(<$contract>::new(initial_supply))
+            }
+
+            fn total_supply(&self) -> $crate::Balance {
+                <$contract>::total_supply(&(*self)$($field)*)
+            }
+
+            fn balance_of(&self, owner: $crate::AccountId) -> $crate::Balance {
+                <$contract>::balance_of(&(*self)$($field)*, owner)
+            }
+
+            fn allowance(
+                &self,
+                owner: $crate::AccountId,
+                spender: $crate::AccountId,
+            ) -> $crate::Balance {
+                <$contract>::allowance(&(*self)$($field)*, owner, spender)
+            }
+
+            fn transfer(
+                &mut self,
+                to: $crate::AccountId,
+                value: $crate::Balance,
+            ) -> ::core::result::Result<(), $error> {
+                <$contract>::transfer(&mut (*self)$($field)*, to, value)
+            }
+
+            fn approve(
+                &mut self,
+                spender: $crate::AccountId,
+                value: $crate::Balance,
+            ) -> ::core::result::Result<(), $error> {
+                <$contract>::approve(&mut (*self)$($field)*, spender, value)
+            }
+
+            fn transfer_from(
+                &mut self,
+                from: $crate::AccountId,
+                to: $crate::AccountId,
+                value: $crate::Balance,
+            ) -> ::core::result::Result<(), $error> {
+                <$contract>::transfer_from(&mut (*self)$($field)*, from, to, value)
+            }
+        }
+    };
+}
+
+/// A call to an ERC-20 contract, the accounts are indices of the default accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erc20Op {
+    Transfer {
+        caller: usize,
+        to: usize,
+        value: Balance,
+    },
+    Approve {
+        caller: usize,
+        spender: usize,
+        value: Balance,
+    },
+    TransferFrom {
+        caller: usize,
+        from: usize,
+        to: usize,
+        value: Balance,
+    },
+}
+
+impl Erc20Op {
+    /// Returns the index of the caller of the message.
+    pub fn caller(&self) -> usize {
+        match *self {
+            Erc20Op::Transfer { caller, .. }
+            | Erc20Op::Approve { caller, .. }
+            | Erc20Op::TransferFrom { caller, .. } => caller,
+        }
+    }
+
+    /// Calls the message on `token`, with the caller set.
+    pub fn call<T: Erc20Contract>(&self, token: &mut T) -> Result<(), T::Error> {
+        let accounts = accounts();
+        as_caller(accounts[self.caller()], || {
+            match *self {
+                Erc20Op::Transfer { to, value, .. } => {
+                    token.transfer(accounts[to], value)
+                }
+                Erc20Op::Approve { spender, value, .. } => {
+                    token.approve(accounts[spender], value)
+                }
+                Erc20Op::TransferFrom {
+                    from, to, value, ..
+                } => token.transfer_from(accounts[from], accounts[to], value),
+            }
+        })
+    }
+}
+
+/// Returns the default accounts, indexed by the accounts of `Erc20Op`.
+pub fn accounts() -> [AccountId; ACCOUNTS] {
+    let accounts = default_accounts::<DefaultEnv>();
+    [
+        accounts.alice,
+        accounts.bob,
+        accounts.charlie,
+        accounts.django,
+        accounts.eve,
+        accounts.frank,
+    ]
+}
+
+/// Generates a call, the values range over the initial supply and a bit more so
+/// that some of the calls fail.
+pub fn erc20_op() -> impl Strategy<Value = Erc20Op> {
+    let account = 0..ACCOUNTS;
+    let value = prop_oneof![0..=10 as Balance, 0..=INITIAL_SUPPLY + 10];
+    prop_oneof![
+        (account.clone(), account.clone(), value.clone())
+            .prop_map(|(caller, to, value)| Erc20Op::Transfer { caller, to, value }),
+        (account.clone(), account.clone(), value.clone()).prop_map(
+            |(caller, spender, value)| Erc20Op::Approve {
+                caller,
+                spender,
+                value,
+            }
+        ),
+        (account.clone(), account.clone(), account, value).prop_map(
+            |(caller, from, to, value)| Erc20Op::TransferFrom {
+                caller,
+                from,
+                to,
+                value,
+            }
+        ),
+    ]
+}
+
+/// Generates a sequence of up to `max_len` calls.
+pub fn erc20_ops(max_len: usize) -> impl Strategy<Value = Vec<Erc20Op>> {
+    vec(erc20_op(), 1..=max_len)
+}
+
+/// The state of an ERC-20 contract over the default accounts, also the model the
+/// contracts are checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20State {
+    pub total_supply: Balance,
+    pub balances: [Balance; ACCOUNTS],
+    /// The allowances, indexed by owner then spender.
+    pub allowances: [[Balance; ACCOUNTS]; ACCOUNTS],
+}
+
+impl Erc20State {
+    /// Returns the state of a token just created by Alice.
+    pub fn new(initial_supply: Balance) -> Self {
+        let mut balances = [0; ACCOUNTS];
+        balances[0] = initial_supply;
+        Self {
+            total_supply: initial_supply,
+            balances,
+            allowances: [[0; ACCOUNTS]; ACCOUNTS],
+        }
+    }
+
+    /// Reads the state of `token`.
+    pub fn of<T: Erc20Contract>(token: &T) -> Self {
+        let accounts = accounts();
+        let mut state = Self::new(token.total_supply());
+        for (owner, owner_account) in accounts.iter().enumerate() {
+            state.balances[owner] = token.balance_of(*owner_account);
+            for (spender, spender_account) in accounts.iter().enumerate() {
+                state.allowances[owner][spender] =
+                    token.allowance(*owner_account, *spender_account);
+            }
+        }
+        state
+    }
+
+    /// Applies `op` to the model, returns the name of the error if it fails.
+    pub fn apply(&mut self, op: &Erc20Op) -> Result<(), &'static str> {
+        match *op {
+            Erc20Op::Transfer { caller, to, value } => {
+                self.move_balance(caller, to, value)
+            }
+            Erc20Op::Approve {
+                caller,
+                spender,
+                value,
+            } => {
+                self.allowances[caller][spender] = value;
+                Ok(())
+            }
+            Erc20Op::TransferFrom {
+                caller,
+                from,
+                to,
+                value,
+            } => {
+                let allowance = self.allowances[from][caller];
+                if allowance < value {
+                    return Err("InsufficientAllowance")
+                }
+                self.move_balance(from, to, value)?;
+                self.allowances[from][caller] = allowance - value;
+                Ok(())
+            }
+        }
+    }
+
+    fn move_balance(
+        &mut self,
+        from: usize,
+        to: usize,
+        value: Balance,
+    ) -> Result<(), &'static str> {
+        if self.balances[from] < value {
+            return Err("InsufficientBalance")
+        }
+        self.balances[from] -= value;
+        self.balances[to] += value;
+        Ok(())
+    }
+}
+
+/// Runs `ops` on a new token `T` in a fresh off-chain environment and checks after
+/// each call that:
+///
+/// - the balances add up to the total supply,
+/// - `transfer_from` never increases an allowance,
+/// - a failed call changes no state and emits no event, a successful one emits one,
+/// - the contract agrees with the model, results and state.
+pub fn check_erc20_ops<T: Erc20Contract>(ops: &[Erc20Op]) {
+    ::ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let mut token = T::new(INITIAL_SUPPLY);
+        let mut model = Erc20State::new(INITIAL_SUPPLY);
+        assert_eq!(Erc20State::of(&token), model, "invalid state after new");
+
+        for (step, op) in ops.iter().enumerate() {
+            let before = Erc20State::of(&token);
+            let events_before = recorded_events_count();
+            let result = op.call(&mut token);
+            let events = recorded_events_count() - events_before;
+            let after = Erc20State::of(&token);
+
+            assert_eq!(
+                after.balances.iter().sum::<Balance>(),
+                after.total_supply,
+                "balances do not add up to the total supply at step {}: {:?}",
+                step,
+                op
+            );
+            if let Erc20Op::TransferFrom { caller, from, .. } = *op {
+                assert!(
+                    after.allowances[from][caller] <= before.allowances[from][caller],
+                    "allowance increased at step {}: {:?}",
+                    step,
+                    op
+                );
+            }
+            if result.is_ok() {
+                assert_eq!(events, 1, "invalid events at step {}: {:?}", step, op);
+            } else {
+                assert_eq!(
+                    after, before,
+                    "failed call changed state at step {}: {:?}",
+                    step, op
+                );
+                assert_eq!(
+                    events, 0,
+                    "failed call emitted events at step {}: {:?}",
+                    step, op
+                );
+            }
+
+            let expected = model.apply(op);
+            assert_eq!(
+                result.map_err(|error| format!("{:?}", error)),
+                expected.map_err(String::from),
+                "invalid result at step {}: {:?}",
+                step,
+                op
+            );
+            assert_eq!(after, model, "invalid state at step {}: {:?}", step, op);
+        }
+        Ok(())
+    })
+    .expect("Cannot run the off-chain environment")
+}
+
+fn recorded_events_count() -> usize {
+    ::ink_env::test::recorded_events().count()
+}
+
+/// The outcome of a call in a trace of `trace_erc20_ops`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20Step {
+    /// The result, with the error by its name.
+    pub result: Result<(), String>,
+    /// The state after the call.
+    pub state: Erc20State,
+    /// The SCALE-encoded events emitted by the call. The topics are left out, their
+    /// signature topic holds the name of the contract.
+    pub events: Vec<Vec<u8>>,
+}
+
+/// Runs `ops` on a new token `T` in a fresh off-chain environment and returns the
+/// outcome of each call, the constructor first.
+pub fn trace_erc20_ops<T: Erc20Contract>(ops: &[Erc20Op]) -> Vec<Erc20Step> {
+    let mut trace = Vec::with_capacity(ops.len() + 1);
+    ::ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let mut events_before = 0;
+        let mut step = |result: Result<(), String>, state: Erc20State| {
+            let events = ::ink_env::test::recorded_events()
+                .skip(events_before)
+                .map(|event| event.data)
+                .collect::<Vec<_>>();
+            events_before += events.len();
+            trace.push(Erc20Step {
+                result,
+                state,
+                events,
+            });
+        };
+
+        let mut token = T::new(INITIAL_SUPPLY);
+        step(Ok(()), Erc20State::of(&token));
+        for op in ops {
+            let result = op.call(&mut token).map_err(|error| format!("{:?}", error));
+            step(result, Erc20State::of(&token));
+        }
+        Ok(())
+    })
+    .expect("Cannot run the off-chain environment");
+    trace
+}
+
+/// Runs `ops` on a new token `A` and a new token `B` and asserts that they behave
+/// the same, reporting the first call where they diverge.
+pub fn assert_same_erc20_ops<A: Erc20Contract, B: Erc20Contract>(ops: &[Erc20Op]) {
+    let trace_a = trace_erc20_ops::<A>(ops);
+    let trace_b = trace_erc20_ops::<B>(ops);
+    let divergence = trace_a
+        .iter()
+        .zip(&trace_b)
+        .position(|(step_a, step_b)| step_a != step_b);
+    if let Some(step) = divergence {
+        let call = match step {
+            0 => format!("new({})", INITIAL_SUPPLY),
+            _ => format!("{:?}", ops[step - 1]),
+        };
+        panic!(
+            "contracts diverge at step {}: {}\n{}: {:?}\n{}: {:?}",
+            step,
+            call,
+            core::any::type_name::<A>(),
+            trace_a[step],
+            core::any::type_name::<B>(),
+            trace_b[step],
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Assertions on the events recorded by the off-chain environment.
+
+use crate::Hash;
+use ::ink_env::{
+    hash::{
+        Blake2x256,
+        CryptoHash,
+        HashOutput,
+    },
+    test::EmittedEvent,
+    Clear,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// For calculating the event topic hash.
+pub struct PrefixedValue<'a, 'b, T> {
+    pub prefix: &'a [u8],
+    pub value: &'b T,
+}
+
+impl<X> Encode for PrefixedValue<'_, '_, X>
+where
+    X: Encode,
+{
+    #[inline]
+    fn size_hint(&self) -> usize {
+        self.prefix.size_hint() + self.value.size_hint()
+    }
+
+    #[inline]
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.prefix.encode_to(dest);
+        self.value.encode_to(dest);
+    }
+}
+
+/// Returns the topic of `entity`: its encoding if it fits in a hash, the
+/// `Blake2x256` hash of its encoding otherwise.
+pub fn encoded_into_hash<T: Encode>(entity: &T) -> Hash {
+    bytes_into_hash(&entity.encode())
+}
+
+fn bytes_into_hash(encoded: &[u8]) -> Hash {
+    let mut result = Hash::clear();
+    let len_result = result.as_ref().len();
+    let len_encoded = encoded.len();
+    if len_encoded <= len_result {
+        result.as_mut()[..len_encoded].copy_from_slice(encoded);
+        return result
+    }
+    let mut hash_output = <<Blake2x256 as HashOutput>::Type as Default>::default();
+    <Blake2x256 as CryptoHash>::hash(encoded, &mut hash_output);
+    let copy_len = core::cmp::min(hash_output.len(), len_result);
+    result.as_mut()[0..copy_len].copy_from_slice(&hash_output[0..copy_len]);
+    result
+}
+
+/// An event struct with its topics, implemented by `event_topics!`.
+pub trait EventTopics {
+    /// The path of the event, `<Contract>::<Event>`.
+    const PATH: &'static str;
+
+    /// Returns the topics of the `#[ink(topic)]` fields, in order.
+    fn field_topics(&self) -> Vec<Hash>;
+
+    /// Returns all the topics of the event, the signature first.
+    fn topics(&self) -> Vec<Hash> {
+        // ink! hashes the path as a byte array after an empty prefix slice, which
+        // encodes as its length, zero. The array encodes as its bytes.
+        let mut signature = vec![0x00];
+        signature.extend_from_slice(Self::PATH.as_bytes());
+        let mut topics = vec![bytes_into_hash(&signature)];
+        topics.extend(self.field_topics());
+        topics
+    }
+}
+
+/// Implements `EventTopics` for an event struct from its path and topic fields.
+///
+/// ```ignore
+/// ::contract_test::event_topics!(Transfer, "Erc20::Transfer", [from, to]);
+/// ```
+#[macro_export]
+macro_rules! event_topics {
+    ($event:ident, $path:literal, [$($field:ident),* $(,)?]) => {
+        impl $crate::EventTopics for $event {
+            const PATH: &'static str = $path;
+
+            fn field_topics(&self) -> ::std::vec::Vec<$crate::Hash> {
+                ::std::vec![$(
+                    $crate::encoded_into_hash(&$crate::PrefixedValue {
+                        prefix: ::core::concat!($path, "::", ::core::stringify!($field))
+                            .as_bytes(),
+                        value: &self.$field,
+                    })
+                ),*]
+            }
+        }
+    };
+}
+
+/// Asserts that `event` is the event `expected` of the contract event enum `E`.
+///
+/// The fields are compared by their encoding, and all the topics are checked.
+pub fn assert_event<E, V>(event: &EmittedEvent, expected: V)
+where
+    E: Decode + Encode + From<V>,
+    V: EventTopics,
+{
+    let expected_topics = expected.topics();
+    let decoded_event = <E as Decode>::decode(&mut &event.data[..])
+        .expect("encountered invalid contract event data buffer");
+    assert_eq!(
+        decoded_event.encode(),
+        E::from(expected).encode(),
+        "encountered invalid {} event",
+        V::PATH
+    );
+    assert_eq!(
+        event.topics.len(),
+        expected_topics.len(),
+        "encountered invalid number of topics of {}",
+        V::PATH
+    );
+    for (n, (actual_topic, expected_topic)) in
+        event.topics.iter().zip(expected_topics).enumerate()
+    {
+        let topic = actual_topic
+            .decode::<Hash>()
+            .expect("encountered invalid topic encoding");
+        assert_eq!(
+            topic, expected_topic,
+            "encountered invalid topic at {} of {}",
+            n,
+            V::PATH
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Helpers for the off-chain tests of the contracts.
+//!
+//! The off-chain environment of ink! 3.0.0-rc3 sets the caller and the transferred
+//! balance of a message through its execution context, the helpers push a context
+//! for the duration of a closure and pop it afterwards.
+//!
+//! Contracts calling each other are tested with the `Simulator`, which hosts each of
+//! them in its own off-chain environment. It needs the `test-utils` feature.
+
+mod erc20;
+mod events;
+mod metadata;
+#[cfg(feature = "test-utils")]
+mod simulator;
+
+pub use erc20::{
+    accounts,
+    assert_same_erc20_ops,
+    check_erc20_ops,
+    erc20_op,
+    erc20_ops,
+    trace_erc20_ops,
+    Erc20Contract,
+    Erc20Op,
+    Erc20State,
+    Erc20Step,
+    ACCOUNTS,
+    INITIAL_SUPPLY,
+};
+pub use events::{
+    assert_event,
+    encoded_into_hash,
+    EventTopics,
+    PrefixedValue,
+};
+pub use metadata::{
+    assert_selectors_in,
+    ERC20_MESSAGES,
+};
+#[cfg(feature = "test-utils")]
+pub use simulator::{
+    decode_input,
+    Dispatch,
+    Reply,
+    Simulator,
+};
+/// The pinned selectors of the standard ERC-20 messages, used by `erc20_dispatch!`.
+pub use ::erc20_basic::selectors as erc20_selectors;
+#[doc(hidden)]
+pub use serde_json;
+
+use ::contract::Env;
+use ::ink_env::{
+    call::Selector,
+    test::CallData,
+    DefaultEnvironment,
+    Environment,
+};
+
+/// The account type of the contracts, all of them use the default environment.
+pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
+/// The balance type of the contracts.
+pub type Balance = <DefaultEnvironment as Environment>::Balance;
+/// The hash type of the contracts, also the type of the event topics.
+pub type Hash = <DefaultEnvironment as Environment>::Hash;
+
+/// The `contract::Env` of the default environment, for contracts which are not
+/// built from modules.
+pub enum DefaultEnv {}
+
+impl Env for DefaultEnv {
+    type AccountId = AccountId;
+    type Balance = Balance;
+    type Hash = Hash;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+}
+
+/// The accounts of the off-chain environment.
+pub struct DefaultAccounts<E: Env> {
+    pub alice: E::AccountId,
+    pub bob: E::AccountId,
+    pub charlie: E::AccountId,
+    pub django: E::AccountId,
+    pub eve: E::AccountId,
+    pub frank: E::AccountId,
+}
+
+/// Returns the accounts of the off-chain environment, typed for the contract `E`.
+///
+/// Alice is the caller of the constructors and of the messages outside `as_caller`.
+pub fn default_accounts<E: Env>() -> DefaultAccounts<E>
+where
+    E::AccountId: From<[u8; 32]>,
+{
+    DefaultAccounts {
+        alice: E::AccountId::from([0x01; 32]),
+        bob: E::AccountId::from([0x02; 32]),
+        charlie: E::AccountId::from([0x03; 32]),
+        django: E::AccountId::from([0x04; 32]),
+        eve: E::AccountId::from([0x05; 32]),
+        frank: E::AccountId::from([0x06; 32]),
+    }
+}
+
+/// Returns the account of the contract under test.
+pub fn contract_id() -> AccountId {
+    ::ink_env::account_id::<DefaultEnvironment>().expect("Cannot get contract id")
+}
+
+/// Returns the current caller.
+pub fn caller() -> AccountId {
+    ::ink_env::caller::<DefaultEnvironment>().expect("Cannot get caller")
+}
+
+/// Returns the native balance of `account`.
+pub fn balance_of(account: AccountId) -> Balance {
+    ::ink_env::test::get_account_balance::<DefaultEnvironment>(account)
+        .expect("Cannot get balance")
+}
+
+/// Sets the native balance of `account`.
+///
+/// The off-chain environment does not move the transferred balance of a call, so a
+/// contract paying out native balance must be funded this way.
+pub fn set_balance(account: AccountId, balance: Balance) {
+    ::ink_env::test::set_account_balance::<DefaultEnvironment>(account, balance)
+        .expect("Cannot set balance")
+}
+
+/// Runs `f` with `caller` as the caller of the messages.
+pub fn as_caller<R>(caller: AccountId, f: impl FnOnce() -> R) -> R {
+    with_context(caller, 0, f)
+}
+
+/// Runs `f` with `value` as the transferred balance of the messages, the caller
+/// stays the same.
+pub fn with_value<R>(value: Balance, f: impl FnOnce() -> R) -> R {
+    with_context(caller(), value, f)
+}
+
+/// Advances the block number by `n` blocks.
+pub fn advance_blocks(n: u32) {
+    for _ in 0..n {
+        ::ink_env::test::advance_block::<DefaultEnvironment>()
+            .expect("Cannot advance block");
+    }
+}
+
+/// Returns the current block number.
+pub fn block_number() -> <DefaultEnvironment as Environment>::BlockNumber {
+    ::ink_env::block_number::<DefaultEnvironment>().expect("Cannot get block number")
+}
+
+fn with_context<R>(caller: AccountId, value: Balance, f: impl FnOnce() -> R) -> R {
+    push_context(caller, contract_id(), value);
+    let result = f();
+    ::ink_env::test::pop_execution_context();
+    result
+}
+
+/// Pushes an execution context of a call of `callee` by `caller`, transferring
+/// `value`, to be popped by the caller of this function.
+fn push_context(caller: AccountId, callee: AccountId, value: Balance) {
+    // The call data is not decoded by the off-chain environment.
+    let data = CallData::new(Selector::new([0x00; 4]));
+    ::ink_env::test::push_execution_context::<DefaultEnvironment>(
+        caller,
+        callee,
+        1000000,
+        value,
+        data,
+    );
+}
@@ -0,0 +1,56 @@
+//! Checks of the metadata generated for a contract.
+
+use ::erc20_basic::selectors::*;
+
+/// The standard ERC-20 messages and their pinned selectors.
+pub const ERC20_MESSAGES: [(&str, [u8; 4]); 6] = [
+    ("total_supply", TOTAL_SUPPLY),
+    ("balance_of", BALANCE_OF),
+    ("allowance", ALLOWANCE),
+    ("transfer", TRANSFER),
+    ("approve", APPROVE),
+    ("transfer_from", TRANSFER_FROM),
+];
+
+/// Asserts that the contract under test has each of the messages with its selector,
+/// its metadata is generated by the ink! entry point of the test binary.
+///
+/// `#[ink::contract]` emits the entry point as a `#[no_mangle]` function inside an
+/// anonymous `const _` block, so it can only be reached through its symbol.
+#[macro_export]
+macro_rules! assert_message_selectors {
+    ($messages:expr) => {{
+        extern "Rust" {
+            fn __ink_generate_metadata() -> ::ink_metadata::InkProject;
+        }
+        // SAFETY: The declaration must match the function generated by the
+        // `ink_lang` of the contract, which is `pub fn __ink_generate_metadata() ->
+        // ink_metadata::InkProject` in the pinned 3.0.0-rc3 and takes no arguments.
+        // The symbol exists only in `std` builds of the contract crate, so the macro
+        // belongs in its unit tests, elsewhere the test binary fails to link. Check
+        // the generated code again when updating ink!.
+        let metadata = unsafe { __ink_generate_metadata() };
+        let metadata = $crate::serde_json::to_value(metadata)
+            .expect("Cannot serialize metadata");
+        $crate::assert_selectors_in(&metadata, &$messages)
+    }};
+}
+
+/// Asserts that `metadata` has each of the `messages` with its selector.
+pub fn assert_selectors_in(metadata: &serde_json::Value, messages: &[(&str, [u8; 4])]) {
+    let specs = metadata["spec"]["messages"]
+        .as_array()
+        .expect("Cannot get messages");
+    for (name, selector) in messages.iter() {
+        let spec = specs
+            .iter()
+            .find(|spec| spec["name"][0] == *name)
+            .unwrap_or_else(|| panic!("Cannot find message {}", name));
+        let selector = selector
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        assert_eq!(spec["selector"], format!("0x{}", selector), "{}", name);
+    }
+}
+
@@ -0,0 +1,405 @@
+//! A simulator of contracts calling each other in the off-chain environment.
+//!
+//! Every contract is hosted on its own thread, whose off-chain environment holds the
+//! storage and the events of that contract only. A call pulls the contract from its
+//! storage and pushes it back when it returns, so a call which traps, i.e. panics,
+//! leaves the contract as it was. The calls made with `contract::invoke` are routed
+//! to the hosted callee, with the calling contract as caller.
+//!
+//! Unlike on chain, a trap rolls back the contract which trapped only, the calls it
+//! made before stay done. Reentrant calls trap, and the native balances are kept
+//! by each environment: the callee sees the transferred value but it is not moved.
+
+use crate::{
+    push_context,
+    AccountId,
+    Balance,
+};
+use ::ink_env::{
+    test::EmittedEvent,
+    DefaultEnvironment,
+    Error,
+};
+use ::ink_primitives::Key;
+use ::ink_storage::traits::{
+    pull_spread_root,
+    push_spread_root,
+    SpreadLayout,
+};
+use scale::Decode;
+use std::{
+    panic::{
+        self,
+        AssertUnwindSafe,
+    },
+    sync::{
+        mpsc::{
+            channel,
+            Receiver,
+            Sender,
+        },
+        Arc,
+        Mutex,
+    },
+    thread::{
+        self,
+        JoinHandle,
+    },
+};
+
+/// The reply to a routed call, the encoded output of the message.
+pub type Reply = Result<Vec<u8>, Error>;
+
+/// Dispatches a routed call to a message of the contract `C`, from its selector and
+/// its encoded arguments.
+///
+/// An unknown selector or arguments which cannot be decoded trap with
+/// `CalleeTrapped`, which rolls back the contract.
+pub type Dispatch<C> = fn(&mut C, [u8; 4], &mut &[u8]) -> Reply;
+
+/// Decodes the arguments of a routed call, arguments which cannot be decoded trap.
+pub fn decode_input<A: Decode>(input: &mut &[u8]) -> Result<A, Error> {
+    A::decode(input).map_err(|_| Error::CalleeTrapped)
+}
+
+/// Returns a `Dispatch` of the standard ERC-20 messages of a token contract.
+///
+/// ```ignore
+/// let token = simulator.deploy(alice, || Erc20::new(1000), erc20_dispatch!(Erc20));
+/// ```
+#[macro_export]
+macro_rules! erc20_dispatch {
+    ($contract:ty) => {
+        |token: &mut $contract, selector: [u8; 4], input: &mut &[u8]| {
+            use $crate::{
+                decode_input,
+                erc20_selectors as selectors,
+            };
+            use ::scale::Encode;
+
+            let output = match selector {
+                selectors::TOTAL_SUPPLY => <$contract>::total_supply(token).encode(),
+                selectors::BALANCE_OF => {
+                    <$contract>::balance_of(token, decode_input(input)?).encode()
+                }
+                selectors::ALLOWANCE => {
+                    let (owner, spender) = decode_input(input)?;
+                    <$contract>::allowance(token, owner, spender).encode()
+                }
+                selectors::TRANSFER => {
+                    let (to, value) = decode_input(input)?;
+                    <$contract>::transfer(token, to, value).encode()
+                }
+                selectors::APPROVE => {
+                    let (spender, value) = decode_input(input)?;
+                    <$contract>::approve(token, spender, value).encode()
+                }
+                selectors::TRANSFER_FROM => {
+                    let (from, to, value) = decode_input(input)?;
+                    <$contract>::transfer_from(token, from, to, value).encode()
+                }
+                _ => return Err(::ink_env::Error::CalleeTrapped),
+            };
+            Ok(output)
+        }
+    };
+}
+
+enum Job {
+    Route {
+        caller: AccountId,
+        value: Balance,
+        input: Vec<u8>,
+        reply: Sender<Reply>,
+    },
+    Run(Box<dyn FnOnce() + Send>),
+    Stop,
+}
+
+#[derive(Default)]
+struct Hosts {
+    /// The hosted contracts, with the senders of the jobs of their threads.
+    jobs: Vec<(AccountId, Sender<Job>)>,
+    /// The contracts in a call, which cannot be called again until it returns.
+    busy: Vec<AccountId>,
+}
+
+impl Hosts {
+    fn jobs(&self, account: AccountId) -> Result<Sender<Job>, Error> {
+        self.jobs
+            .iter()
+            .find(|(hosted, _)| *hosted == account)
+            .map(|(_, jobs)| jobs.clone())
+            .ok_or(Error::NotCallable)
+    }
+
+    /// Marks `account` as in a call and returns the sender of its jobs.
+    fn enter(&mut self, account: AccountId) -> Result<Sender<Job>, Error> {
+        let jobs = self.jobs(account)?;
+        if self.busy.contains(&account) {
+            return Err(Error::CalleeTrapped)
+        }
+        self.busy.push(account);
+        Ok(jobs)
+    }
+
+    fn leave(&mut self, account: AccountId) {
+        self.busy.retain(|busy| *busy != account);
+    }
+}
+
+/// Hosts contracts, each in its own off-chain environment, and routes the calls
+/// between them.
+///
+/// The contracts are deployed at the accounts `[0x80; 32]`, `[0x81; 32]`, etc. in
+/// the order of `deploy`.
+pub struct Simulator {
+    hosts: Arc<Mutex<Hosts>>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self {
+            hosts: Arc::new(Mutex::new(Hosts::default())),
+            threads: Vec::new(),
+        }
+    }
+
+    /// Deploys the contract created by `new`, called by `caller`, and returns its
+    /// account. The calls routed to the contract are passed to `dispatch`.
+    pub fn deploy<C, F>(
+        &mut self,
+        caller: AccountId,
+        new: F,
+        dispatch: Dispatch<C>,
+    ) -> AccountId
+    where
+        C: SpreadLayout + 'static,
+        F: FnOnce() -> C + Send + 'static,
+    {
+        let account = AccountId::from([0x80 + self.threads.len() as u8; 32]);
+        let (jobs, receiver) = channel();
+        let hosts = Arc::clone(&self.hosts);
+        self.threads.push(thread::spawn(move || {
+            host(hosts, account, dispatch, receiver)
+        }));
+        self.hosts.lock().unwrap().jobs.push((account, jobs));
+
+        self.run(account, move || {
+            push_context(caller, account, 0);
+            push_spread_root(&new(), &root_key());
+            ::ink_env::test::pop_execution_context();
+        });
+        account
+    }
+
+    /// Calls `f` on the contract `C` at `contract`, with `caller` as caller and
+    /// `value` as transferred balance.
+    ///
+    /// If `f` panics, the contract is rolled back and the panic is resumed here.
+    pub fn call<C, R, F>(
+        &self,
+        contract: AccountId,
+        caller: AccountId,
+        value: Balance,
+        f: F,
+    ) -> R
+    where
+        C: SpreadLayout + 'static,
+        R: Send + 'static,
+        F: FnOnce(&mut C) -> R + Send + 'static,
+    {
+        self.call_with(contract, caller, value, f, |_| true)
+    }
+
+    /// Calls `f` like `call`, the contract is also rolled back if `f` returns an
+    /// error.
+    pub fn try_call<C, T, E, F>(
+        &self,
+        contract: AccountId,
+        caller: AccountId,
+        value: Balance,
+        f: F,
+    ) -> Result<T, E>
+    where
+        C: SpreadLayout + 'static,
+        T: Send + 'static,
+        E: Send + 'static,
+        F: FnOnce(&mut C) -> Result<T, E> + Send + 'static,
+    {
+        self.call_with(contract, caller, value, f, Result::is_ok)
+    }
+
+    /// Runs `f` in the environment of `contract`, e.g. to read its events.
+    pub fn run<R, F>(&self, contract: AccountId, f: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+    {
+        let jobs = self
+            .hosts
+            .lock()
+            .unwrap()
+            .jobs(contract)
+            .expect("no contract is hosted at the account");
+        resume(send(&jobs, move || panic::catch_unwind(AssertUnwindSafe(f))))
+    }
+
+    /// Returns the events emitted by `contract`.
+    pub fn events(&self, contract: AccountId) -> Vec<EmittedEvent> {
+        self.run(contract, || ::ink_env::test::recorded_events().collect())
+    }
+
+    fn call_with<C, R, F>(
+        &self,
+        contract: AccountId,
+        caller: AccountId,
+        value: Balance,
+        f: F,
+        commit: fn(&R) -> bool,
+    ) -> R
+    where
+        C: SpreadLayout + 'static,
+        R: Send + 'static,
+        F: FnOnce(&mut C) -> R + Send + 'static,
+    {
+        let jobs = self
+            .hosts
+            .lock()
+            .unwrap()
+            .enter(contract)
+            .expect("no contract is hosted at the account or it is in a call");
+        let result = send(&jobs, move || {
+            call_stored::<C, R, F>(caller, contract, value, f, commit)
+        });
+        self.hosts.lock().unwrap().leave(contract);
+        resume(result)
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        for (_, jobs) in self.hosts.lock().unwrap().jobs.drain(..) {
+            let _ = jobs.send(Job::Stop);
+        }
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The root key of the contracts, the one of the dispatch of ink!.
+fn root_key() -> Key {
+    Key::from([0x00; 32])
+}
+
+/// Runs `f` on the thread of `jobs` and returns its result.
+fn send<R, F>(jobs: &Sender<Job>, f: F) -> thread::Result<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> thread::Result<R> + Send + 'static,
+{
+    let (reply, receiver) = channel();
+    jobs.send(Job::Run(Box::new(move || {
+        let _ = reply.send(f());
+    })))
+    .expect("the contract host stopped");
+    receiver.recv().expect("the contract host stopped")
+}
+
+fn resume<R>(result: thread::Result<R>) -> R {
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Calls `f` on the contract stored in the environment of the current thread, and
+/// stores it back if `f` returns and `commit` accepts its output.
+fn call_stored<C, R, F>(
+    caller: AccountId,
+    callee: AccountId,
+    value: Balance,
+    f: F,
+    commit: impl FnOnce(&R) -> bool,
+) -> thread::Result<R>
+where
+    C: SpreadLayout,
+    F: FnOnce(&mut C) -> R,
+{
+    push_context(caller, callee, value);
+    let mut contract = pull_spread_root::<C>(&root_key());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut contract)));
+    if let Ok(output) = &result {
+        if commit(output) {
+            push_spread_root(&contract, &root_key());
+        }
+    }
+    ::ink_env::test::pop_execution_context();
+    result
+}
+
+/// Runs the jobs of the contract at `account` until the simulator stops.
+fn host<C: SpreadLayout>(
+    hosts: Arc<Mutex<Hosts>>,
+    account: AccountId,
+    dispatch: Dispatch<C>,
+    jobs: Receiver<Job>,
+) {
+    ::ink_env::test::initialize_or_reset_as_default::<DefaultEnvironment>()
+        .expect("Cannot initialize the off-chain environment");
+    let router_hosts = Arc::clone(&hosts);
+    ::contract::set_router(move |callee, value, input| {
+        route(&router_hosts, account, callee, value, input)
+    });
+
+    for job in jobs {
+        match job {
+            Job::Route {
+                caller,
+                value,
+                input,
+                reply,
+            } => {
+                let call = |contract: &mut C| {
+                    let mut input = &input[..];
+                    let selector = decode_input::<[u8; 4]>(&mut input)?;
+                    dispatch(contract, selector, &mut input)
+                };
+                let result = call_stored(caller, account, value, call, Result::is_ok)
+                    .unwrap_or(Err(Error::CalleeTrapped));
+                let _ = reply.send(result);
+            }
+            Job::Run(f) => f(),
+            Job::Stop => break,
+        }
+    }
+}
+
+/// Routes a call of `caller` to the hosted contract `callee`, both encoded.
+fn route(
+    hosts: &Mutex<Hosts>,
+    caller: AccountId,
+    callee: &[u8],
+    value: &[u8],
+    input: Vec<u8>,
+) -> Reply {
+    let callee = AccountId::decode(&mut &callee[..]).map_err(Error::Decode)?;
+    let value = Balance::decode(&mut &value[..]).map_err(Error::Decode)?;
+    let jobs = hosts.lock().unwrap().enter(callee)?;
+    let (reply, receiver) = channel();
+    jobs.send(Job::Route {
+        caller,
+        value,
+        input,
+        reply,
+    })
+    .expect("the contract host stopped");
+    let result = receiver.recv().expect("the contract host stopped");
+    hosts.lock().unwrap().leave(callee);
+    result
+}
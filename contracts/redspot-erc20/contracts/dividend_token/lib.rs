@@ -0,0 +1,381 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// An ERC-20 token paying native-currency dividends to its holders.
+#[ink::contract]
+pub mod dividend_token {
+    use ::ink_lang::{
+        EmitEvent,
+        Env,
+        StaticEnv,
+    };
+
+    use ::dividends::{
+        Data as DividendsData,
+        DividendsEventEmit,
+        DividendsImpl,
+        Result as DividendsResult,
+        TransferAccess,
+    };
+    use ::erc20_basic::{
+        Data as Erc20Data,
+        Erc20EventEmit,
+        Erc20Impl,
+        Result,
+    };
+
+    #[ink(storage)]
+    pub struct DividendToken {
+        data_erc20: ::contract::Namespaced<Erc20Data<DividendToken>>,
+        data_dividends: ::contract::Namespaced<DividendsData<DividendToken>>,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` tokens from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `value` is distributed to the holders.
+    #[ink(event)]
+    pub struct DividendsDistributed {
+        #[ink(topic)]
+        from: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when a holder withdraws its dividends.
+    #[ink(event)]
+    pub struct DividendWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    // TODO: Make by macro
+    use ::contract::Module;
+    impl ::contract::Env for DividendToken {
+        type AccountId = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::AccountId;
+        type Balance = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Balance;
+        type Hash = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Hash;
+        type Timestamp =
+            <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Timestamp;
+        type BlockNumber =
+            <::ink_env::DefaultEnvironment as ::ink_env::Environment>::BlockNumber;
+    }
+
+    impl ::contract::EnvAccess<DividendToken> for DividendToken {
+        fn caller() -> <DividendToken as ::contract::Env>::AccountId {
+            Self::env().caller()
+        }
+
+        fn transferred_balance() -> <DividendToken as ::contract::Env>::Balance {
+            Self::env().transferred_balance()
+        }
+    }
+
+    impl ::contract::Module<Erc20Data<DividendToken>> for DividendToken {
+        fn get_module(&self) -> &Erc20Data<DividendToken> {
+            &self.data_erc20
+        }
+
+        fn get_module_mut(&mut self) -> &mut Erc20Data<DividendToken> {
+            &mut self.data_erc20
+        }
+    }
+
+    impl ::contract::Module<DividendsData<DividendToken>> for DividendToken {
+        fn get_module(&self) -> &DividendsData<DividendToken> {
+            &self.data_dividends
+        }
+
+        fn get_module_mut(&mut self) -> &mut DividendsData<DividendToken> {
+            &mut self.data_dividends
+        }
+    }
+
+    impl TransferAccess<DividendToken> for DividendToken {
+        fn transfer(to: AccountId, value: Balance) -> bool {
+            Self::env().transfer(to, value).is_ok()
+        }
+    }
+    // TODO: Make by macro
+
+    // TODO: Event in ink!
+    impl Erc20EventEmit<DividendToken> for DividendToken {
+        fn emit_event_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
+        ) {
+            self.env().emit_event(Transfer { from, to, value });
+        }
+
+        fn emit_event_approval(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) {
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+        }
+
+        fn after_token_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
+        ) {
+            self.on_token_transfer_impl(from, to, value);
+        }
+    }
+
+    impl DividendsEventEmit<DividendToken> for DividendToken {
+        fn emit_event_dividends_distributed(&mut self, from: AccountId, value: Balance) {
+            self.env().emit_event(DividendsDistributed { from, value });
+        }
+
+        fn emit_event_dividend_withdrawn(&mut self, to: AccountId, value: Balance) {
+            self.env().emit_event(DividendWithdrawn { to, value });
+        }
+    }
+    // TODO: Event in ink!
+
+    impl DividendToken {
+        /// Creates a new dividend token with the specified initial supply.
+        #[ink(constructor, selector = "0x9BAE9D5E")]
+        pub fn new(initial_supply: Balance) -> Self {
+            let mut instance = Self {
+                data_erc20: ::contract::Namespaced::init(Erc20Data::new()),
+                data_dividends: ::contract::Namespaced::init(DividendsData::new()),
+            };
+            instance.new_impl(initial_supply);
+            instance
+        }
+
+        /// Returns the total token supply.
+        #[ink(message, selector = "0xDB6375A8")]
+        pub fn total_supply(&self) -> Balance {
+            Module::<Erc20Data<DividendToken>>::get_module(self).get_total_supply()
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message, selector = "0x0F755A56")]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            Module::<Erc20Data<DividendToken>>::get_module(self).get_balance(owner)
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        #[ink(message, selector = "0x6A00165E")]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            Module::<Erc20Data<DividendToken>>::get_module(self)
+                .get_allowance(owner, spender)
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`,
+        /// the dividends distributed before stay with the caller.
+        #[ink(message, selector = "0x84A15DA1")]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            self.transfer_impl(to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        #[ink(message, selector = "0x681266A0")]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            self.approve_impl(spender, value)
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        #[ink(message, selector = "0x0B396F18")]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.transfer_from_impl(from, to, value)
+        }
+
+        /// Distributes the transferred balance to the holders, in proportion to their
+        /// balance.
+        ///
+        /// On success a `DividendsDistributed` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ZeroAmount` if no balance was transferred and `NoSupply` if there
+        /// are no tokens, the transferred balance is then sent back.
+        #[ink(message, payable)]
+        pub fn distribute(&mut self) -> DividendsResult<()> {
+            self.distribute_impl()
+        }
+
+        /// Returns the dividends `owner` can withdraw.
+        #[ink(message)]
+        pub fn withdrawable_dividend_of(&self, owner: AccountId) -> Balance {
+            self.withdrawable_dividend_of_impl(owner)
+        }
+
+        /// Returns the dividends `owner` withdrew.
+        #[ink(message)]
+        pub fn withdrawn_dividend_of(&self, owner: AccountId) -> Balance {
+            self.withdrawn_dividend_of_impl(owner)
+        }
+
+        /// Transfers the withdrawable dividends of the caller to it.
+        ///
+        /// On success a `DividendWithdrawn` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NothingToWithdraw` if the caller has no dividends.
+        #[ink(message)]
+        pub fn withdraw_dividend(&mut self) -> DividendsResult<Balance> {
+            self.withdraw_dividend_impl()
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ::contract_test::{
+            as_caller,
+            balance_of,
+            contract_id,
+            default_accounts,
+            set_balance,
+            with_value,
+        };
+        use ::dividends::Error;
+
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn distribute_works() {
+            let accounts = default_accounts::<DividendToken>();
+            let mut token = DividendToken::new(100);
+            assert_eq!(token.transfer(accounts.bob, 25), Ok(()));
+
+            as_caller(accounts.eve, || {
+                assert_eq!(with_value(1000, || token.distribute()), Ok(()));
+            });
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 750);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 250);
+            assert_eq!(token.withdrawable_dividend_of(accounts.eve), 0);
+        }
+
+        #[ink::test]
+        fn transfers_keep_distributed_dividends() {
+            let accounts = default_accounts::<DividendToken>();
+            let mut token = DividendToken::new(100);
+            assert_eq!(with_value(1000, || token.distribute()), Ok(()));
+
+            // Alice keeps the dividends of the tokens she sends away.
+            assert_eq!(token.transfer(accounts.bob, 40), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 1000);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 0);
+
+            assert_eq!(with_value(500, || token.distribute()), Ok(()));
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 1300);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 200);
+        }
+
+        #[ink::test]
+        fn withdraw_dividend_works() {
+            let accounts = default_accounts::<DividendToken>();
+            let mut token = DividendToken::new(100);
+            assert_eq!(token.transfer(accounts.bob, 25), Ok(()));
+            // The off-chain environment does not move the transferred balance.
+            set_balance(contract_id(), 1000);
+            assert_eq!(with_value(1000, || token.distribute()), Ok(()));
+
+            let bob_balance = balance_of(accounts.bob);
+            as_caller(accounts.bob, || {
+                assert_eq!(token.withdraw_dividend(), Ok(250));
+                assert_eq!(token.withdraw_dividend(), Err(Error::NothingToWithdraw));
+            });
+            assert_eq!(balance_of(accounts.bob), bob_balance + 250);
+            assert_eq!(token.withdrawable_dividend_of(accounts.bob), 0);
+            assert_eq!(token.withdrawn_dividend_of(accounts.bob), 250);
+
+            // Alice's dividends are untouched.
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 750);
+        }
+
+        #[ink::test]
+        fn failed_distribute_refunds_value() {
+            let accounts = default_accounts::<DividendToken>();
+            let mut token = DividendToken::new(0);
+            set_balance(contract_id(), 1000);
+            let eve_balance = balance_of(accounts.eve);
+
+            as_caller(accounts.eve, || {
+                assert_eq!(token.distribute(), Err(Error::ZeroAmount));
+                assert_eq!(with_value(100, || token.distribute()), Err(Error::NoSupply));
+            });
+            assert_eq!(balance_of(accounts.eve), eve_balance + 100);
+        }
+
+        #[ink::test]
+        fn distribute_too_small_refunds_value() {
+            let accounts = default_accounts::<DividendToken>();
+            // Even 1 magnified by 2^64 is less than the supply.
+            let mut token = DividendToken::new(Balance::MAX);
+            set_balance(contract_id(), 1);
+            let eve_balance = balance_of(accounts.eve);
+
+            as_caller(accounts.eve, || {
+                assert_eq!(
+                    with_value(1, || token.distribute()),
+                    Err(Error::AmountTooSmall)
+                );
+            });
+            assert_eq!(balance_of(accounts.eve), eve_balance + 1);
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn distribute_overflow_refunds_value() {
+            let accounts = default_accounts::<DividendToken>();
+            let mut token = DividendToken::new(100);
+            // The value magnified by 2^64 does not fit in 128 bits.
+            let value = (1 << 64) + 1;
+            set_balance(contract_id(), value);
+            let eve_balance = balance_of(accounts.eve);
+
+            as_caller(accounts.eve, || {
+                assert_eq!(
+                    with_value(value, || token.distribute()),
+                    Err(Error::Overflow)
+                );
+            });
+            assert_eq!(balance_of(accounts.eve), eve_balance + value);
+            assert_eq!(token.withdrawable_dividend_of(accounts.alice), 0);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod module;
+
+pub use ::contract::{
+    Env,
+    EnvAccess,
+};
+
+pub use module::Data;
+
+/// Native-currency dividends for the holders of an `erc20_basic` token.
+///
+/// Dividends use the "magnified dividend per share" technique: a distribution only
+/// raises the dividends per token, and each transfer corrects the dividends of both
+/// accounts so that tokens do not carry the dividends distributed before they moved.
+/// Both are O(1) whatever the number of holders.
+mod dividends {
+    use super::Data;
+    use ::contract::{
+        Env,
+        EnvAccess,
+        Module,
+    };
+    use ::erc20_basic::Data as Erc20Data;
+    use core::convert::TryInto;
+
+    /// The factor of the magnified dividends, which keeps the remainders of the
+    /// divisions by the total supply.
+    pub const MAGNITUDE: u128 = 1 << 64;
+
+    /// The dividends error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if no native balance was transferred to distribute.
+        ZeroAmount,
+        /// Returned if there are no tokens to distribute the dividends to.
+        NoSupply,
+        /// Returned if the dividends per token overflow.
+        Overflow,
+        /// Returned if the value is too small to raise the dividends per token.
+        AmountTooSmall,
+        /// Returned if the caller has no dividends to withdraw.
+        NothingToWithdraw,
+        /// Returned if the native balance could not be transferred.
+        TransferFailed,
+    }
+
+    /// The dividends result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    pub trait DividendsEventEmit<E: Env>: EnvAccess<E> {
+        fn emit_event_dividends_distributed(
+            &mut self,
+            from: E::AccountId,
+            value: E::Balance,
+        );
+
+        fn emit_event_dividend_withdrawn(&mut self, to: E::AccountId, value: E::Balance);
+    }
+
+    /// Access to the native balance of the contract.
+    pub trait TransferAccess<E: Env>: EnvAccess<E> {
+        /// Transfers `value` of the contract's balance to `to`, returns `false` if the
+        /// transfer failed.
+        fn transfer(to: E::AccountId, value: E::Balance) -> bool;
+    }
+
+    pub trait DividendsImpl<E: Env>:
+        Module<Data<E>> + Module<Erc20Data<E>> + DividendsEventEmit<E> + TransferAccess<E>
+    {
+        /// Distributes the transferred balance to the token holders, in proportion
+        /// to their balance.
+        ///
+        /// The transferred balance is sent back to the caller on error. The remainder
+        /// of the division by the total supply stays in the contract.
+        fn distribute_impl(&mut self) -> Result<()> {
+            let from = Self::caller();
+            let value = Self::transferred_balance();
+            match self.next_magnified_dividend_per_share(value) {
+                Ok(per_share) => {
+                    <Self as Module<Data<E>>>::get_module_mut(self)
+                        .set_magnified_dividend_per_share(per_share);
+                    self.emit_event_dividends_distributed(from, value);
+                    Ok(())
+                }
+                Err(error) => {
+                    if value != E::Balance::from(0 as u8) {
+                        assert!(Self::transfer(from, value), "refund cannot fail");
+                    }
+                    Err(error)
+                }
+            }
+        }
+
+        /// Returns the dividends `owner` can withdraw.
+        fn withdrawable_dividend_of_impl(&self, owner: E::AccountId) -> E::Balance {
+            let withdrawn =
+                <Self as Module<Data<E>>>::get_module(self).get_withdrawn(&owner);
+            from_u128::<E>(
+                self.accumulative_dividend_of(&owner)
+                    .saturating_sub(withdrawn),
+            )
+        }
+
+        /// Returns the dividends `owner` withdrew.
+        fn withdrawn_dividend_of_impl(&self, owner: E::AccountId) -> E::Balance {
+            let withdrawn =
+                <Self as Module<Data<E>>>::get_module(self).get_withdrawn(&owner);
+            from_u128::<E>(withdrawn)
+        }
+
+        /// Transfers the withdrawable dividends of the caller to it.
+        ///
+        /// Returns the withdrawn dividends.
+        fn withdraw_dividend_impl(&mut self) -> Result<E::Balance> {
+            let to = Self::caller();
+            let value = self.withdrawable_dividend_of_impl(to.clone());
+            if value == E::Balance::from(0 as u8) {
+                return Err(Error::NothingToWithdraw)
+            }
+            let withdrawn =
+                <Self as Module<Data<E>>>::get_module(self).get_withdrawn(&to);
+            <Self as Module<Data<E>>>::get_module_mut(self)
+                .withdrawn_insert(to.clone(), withdrawn + to_u128::<E>(value));
+            if !Self::transfer(to.clone(), value) {
+                <Self as Module<Data<E>>>::get_module_mut(self)
+                    .withdrawn_insert(to, withdrawn);
+                return Err(Error::TransferFailed)
+            }
+            self.emit_event_dividend_withdrawn(to, value);
+            Ok(value)
+        }
+
+        /// Corrects the dividends of the accounts of a token transfer, contracts call
+        /// this from `Erc20EventEmit::after_token_transfer`.
+        fn on_token_transfer_impl(
+            &mut self,
+            from: Option<E::AccountId>,
+            to: Option<E::AccountId>,
+            value: E::Balance,
+        ) {
+            let data = <Self as Module<Data<E>>>::get_module_mut(self);
+            let correction = data
+                .get_magnified_dividend_per_share()
+                .wrapping_mul(to_u128::<E>(value)) as i128;
+            if let Some(from) = from {
+                let current = data.get_magnified_correction(&from);
+                data.magnified_correction_insert(from, current.wrapping_add(correction));
+            }
+            if let Some(to) = to {
+                let current = data.get_magnified_correction(&to);
+                data.magnified_correction_insert(to, current.wrapping_sub(correction));
+            }
+        }
+
+        /// Returns the dividends per token after distributing `value`.
+        fn next_magnified_dividend_per_share(&self, value: E::Balance) -> Result<u128> {
+            if value == E::Balance::from(0 as u8) {
+                return Err(Error::ZeroAmount)
+            }
+            let total_supply = to_u128::<E>(
+                <Self as Module<Erc20Data<E>>>::get_module(self).get_total_supply(),
+            );
+            if total_supply == 0 {
+                return Err(Error::NoSupply)
+            }
+            let increase = to_u128::<E>(value)
+                .checked_mul(MAGNITUDE)
+                .ok_or(Error::Overflow)?
+                / total_supply;
+            if increase == 0 {
+                // Nobody would get any of the value, it is refunded instead.
+                return Err(Error::AmountTooSmall)
+            }
+            <Self as Module<Data<E>>>::get_module(self)
+                .get_magnified_dividend_per_share()
+                .checked_add(increase)
+                .ok_or(Error::Overflow)
+        }
+
+        /// Returns all the dividends of `owner`, withdrawn or not.
+        ///
+        /// The magnified dividends are computed modulo 2^128, only the result has to
+        /// fit.
+        fn accumulative_dividend_of(&self, owner: &E::AccountId) -> u128 {
+            let balance = to_u128::<E>(
+                <Self as Module<Erc20Data<E>>>::get_module(self)
+                    .get_balance(owner.clone()),
+            );
+            let data = <Self as Module<Data<E>>>::get_module(self);
+            let magnified = data
+                .get_magnified_dividend_per_share()
+                .wrapping_mul(balance) as i128;
+            let corrected = magnified.wrapping_add(data.get_magnified_correction(owner));
+            corrected as u128 / MAGNITUDE
+        }
+    }
+
+    impl<E, T> DividendsImpl<E> for T
+    where
+        E: Env,
+        T: Module<Data<E>>
+            + Module<Erc20Data<E>>
+            + DividendsEventEmit<E>
+            + TransferAccess<E>,
+    {
+    }
+
+    fn to_u128<E: Env>(value: E::Balance) -> u128 {
+        value
+            .try_into()
+            .unwrap_or_else(|_| panic!("balance does not fit in u128"))
+    }
+
+    fn from_u128<E: Env>(value: u128) -> E::Balance {
+        value
+            .try_into()
+            .unwrap_or_else(|_| panic!("dividend does not fit in the balance"))
+    }
+}
+
+pub use dividends::{
+    DividendsEventEmit,
+    DividendsImpl,
+    Error,
+    Result,
+    TransferAccess,
+    MAGNITUDE,
+};
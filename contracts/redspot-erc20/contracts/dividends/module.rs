@@ -0,0 +1,78 @@
+pub mod module {
+    pub use ::contract::Env;
+    use ::contract::{
+        Migrate,
+        Namespace,
+    };
+
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::{
+        collections::HashMap as StorageHashMap,
+        lazy::Lazy,
+        traits::{
+            SpreadLayout,
+        },
+    };
+
+    #[cfg_attr(feature = "std", derive(::ink_storage::traits::StorageLayout))]
+    #[derive(Debug, SpreadLayout)]
+    pub struct Data<E: Env> {
+        /// Dividends distributed per token, multiplied by `MAGNITUDE`.
+        magnified_dividend_per_share: Lazy<u128>,
+        /// Mapping from holder to the correction of its magnified dividends, which
+        /// offsets the dividends distributed before its balance changed.
+        magnified_corrections: StorageHashMap<E::AccountId, i128>,
+        /// Mapping from holder to its withdrawn dividends.
+        withdrawn: StorageHashMap<E::AccountId, u128>,
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn new() -> Self {
+            Self {
+                magnified_dividend_per_share: Lazy::default(),
+                magnified_corrections: StorageHashMap::new(),
+                withdrawn: StorageHashMap::new(),
+            }
+        }
+    }
+
+    impl<E: Env> Namespace for Data<E> {
+        const NAMESPACE: &'static [u8] = b"dividends::Data";
+    }
+
+    impl<E: Env> Migrate for Data<E> {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, _from: u32) {}
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn get_magnified_dividend_per_share(&self) -> u128 {
+            *self.magnified_dividend_per_share
+        }
+
+        pub fn set_magnified_dividend_per_share(&mut self, value: u128) {
+            Lazy::set(&mut self.magnified_dividend_per_share, value);
+        }
+
+        pub fn get_magnified_correction(&self, owner: &E::AccountId) -> i128 {
+            self.magnified_corrections.get(owner).copied().unwrap_or(0)
+        }
+
+        pub fn magnified_correction_insert(&mut self, owner: E::AccountId, value: i128) {
+            self.magnified_corrections.insert(owner, value);
+        }
+
+        pub fn get_withdrawn(&self, owner: &E::AccountId) -> u128 {
+            self.withdrawn.get(owner).copied().unwrap_or(0)
+        }
+
+        pub fn withdrawn_insert(&mut self, owner: E::AccountId, value: u128) {
+            self.withdrawn.insert(owner, value);
+        }
+    }
+}
+
+pub use module::{
+    Data,
+};
@@ -0,0 +1,638 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+pub mod erc1155 {
+    use ::ink_lang::{
+        EmitEvent,
+        Env,
+        StaticEnv,
+    };
+    use ::ink_prelude::vec::Vec;
+
+    use ::erc1155_basic::{
+        Data as Erc1155Data,
+        Erc1155EventEmit,
+        Erc1155Impl,
+        Result,
+        TokenId,
+    };
+
+    /// A simple ERC-1155 multi-token contract.
+    #[ink(storage)]
+    pub struct Erc1155 {
+        data_erc1155: ::contract::Namespaced<Erc1155Data<Erc1155>>,
+    }
+
+    /// Event emitted when a single token type is transferred, minted or burned.
+    #[ink(event)]
+    pub struct TransferSingle {
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        id: TokenId,
+        value: Balance,
+    }
+
+    /// Event emitted when several token types are transferred at once.
+    #[ink(event)]
+    pub struct TransferBatch {
+        #[ink(topic)]
+        operator: AccountId,
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        ids: Vec<TokenId>,
+        values: Vec<Balance>,
+    }
+
+    /// Event emitted when an operator is enabled or disabled for an owner.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    // TODO: Make by macro
+    use ::contract::Module;
+    impl ::contract::Env for Erc1155 {
+        type AccountId = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::AccountId;
+        type Balance = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Balance;
+        type Hash = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Hash;
+        type Timestamp = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Timestamp;
+        type BlockNumber = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::BlockNumber;
+    }
+
+    impl ::contract::EnvAccess<Erc1155> for Erc1155 {
+        fn caller() -> <Erc1155 as ::contract::Env>::AccountId {
+            Self::env().caller()
+        }
+
+        fn transferred_balance() -> <Erc1155 as ::contract::Env>::Balance {
+            Self::env().transferred_balance()
+        }
+    }
+
+    impl ::contract::Module<Erc1155Data<Erc1155>> for Erc1155 {
+        fn get_module(&self) -> &Erc1155Data<Erc1155> {
+            &self.data_erc1155
+        }
+
+        fn get_module_mut(&mut self) -> &mut Erc1155Data<Erc1155> {
+            &mut self.data_erc1155
+        }
+    }
+
+    impl ::contract::ReceiverAccess<Erc1155> for Erc1155 {
+        fn call_receiver<Args: scale::Encode>(
+            to: &AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> Result<[u8; 4], ::ink_env::Error> {
+            ::contract::call_receiver::<::ink_env::DefaultEnvironment, Args>(
+                to,
+                selector,
+                args,
+            )
+        }
+    }
+    // TODO: Make by macro
+
+    // TODO: Event in ink!
+    impl Erc1155EventEmit<Erc1155> for Erc1155 {
+        fn emit_event_transfer_single(
+            &mut self,
+            operator: AccountId,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            id: TokenId,
+            value: Balance,
+        ) {
+            self.env().emit_event(TransferSingle {
+                operator,
+                from,
+                to,
+                id,
+                value,
+            });
+        }
+
+        fn emit_event_transfer_batch(
+            &mut self,
+            operator: AccountId,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            ids: Vec<TokenId>,
+            values: Vec<Balance>,
+        ) {
+            self.env().emit_event(TransferBatch {
+                operator,
+                from,
+                to,
+                ids,
+                values,
+            });
+        }
+
+        fn emit_event_approval_for_all(
+            &mut self,
+            owner: AccountId,
+            operator: AccountId,
+            approved: bool,
+        ) {
+            self.env().emit_event(ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            });
+        }
+    }
+    // TODO: Event in ink!
+
+    // impl
+    impl Erc1155 {
+        /// Creates a new ERC-1155 multi-token contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                data_erc1155: ::contract::Namespaced::init(Erc1155Data::new()),
+            }
+        }
+
+        /// Returns the balance of `owner` for the token type `id`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId, id: TokenId) -> Balance {
+            self.balance_of_impl(owner, id)
+        }
+
+        /// Returns the balances of each `(owners[i], ids[i])` pair.
+        ///
+        /// # Errors
+        ///
+        /// Returns `BatchTransferMismatch` error if `owners` and `ids` differ in length.
+        #[ink(message)]
+        pub fn balance_of_batch(
+            &self,
+            owners: Vec<AccountId>,
+            ids: Vec<TokenId>,
+        ) -> Result<Vec<Balance>> {
+            self.balance_of_batch_impl(owners, ids)
+        }
+
+        /// Returns `true` if `operator` may manage all tokens of `owner`.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.is_approved_for_all_impl(owner, operator)
+        }
+
+        /// Approves or disapproves `operator` to manage all tokens of the caller.
+        ///
+        /// An `ApprovalForAll` event is emitted.
+        #[ink(message)]
+        pub fn set_approval_for_all(
+            &mut self,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<()> {
+            self.set_approval_for_all_impl(operator, approved)
+        }
+
+        /// Transfers `value` tokens of type `id` from `from` to `to`.
+        ///
+        /// On success a `TransferSingle` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotApproved` error if the caller is neither `from` nor an approved
+        /// operator of `from`.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        #[ink(message)]
+        pub fn safe_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: TokenId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.safe_transfer_from_impl(from, to, id, value, data)
+        }
+
+        /// Transfers `values[i]` tokens of type `ids[i]` from `from` to `to`.
+        ///
+        /// On success a single `TransferBatch` event is emitted, on failure no balance
+        /// changes.
+        #[ink(message)]
+        pub fn safe_batch_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            ids: Vec<TokenId>,
+            values: Vec<Balance>,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.safe_batch_transfer_from_impl(from, to, ids, values, data)
+        }
+
+        /// Creates `value` new tokens of type `id` on the caller's account.
+        #[ink(message)]
+        pub fn mint(&mut self, id: TokenId, value: Balance) -> Result<()> {
+            self.mint_impl(id, value)
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ::contract_test::{
+            as_caller,
+            assert_event,
+        };
+
+        use ::erc1155_basic::Error;
+
+        type Event = <Erc1155 as ::ink_lang::BaseEvent>::Type;
+
+        use ink_lang as ink;
+
+        ::contract_test::event_topics!(
+            TransferSingle,
+            "Erc1155::TransferSingle",
+            [operator, from, to]
+        );
+        ::contract_test::event_topics!(
+            TransferBatch,
+            "Erc1155::TransferBatch",
+            [operator, from, to]
+        );
+        ::contract_test::event_topics!(
+            ApprovalForAll,
+            "Erc1155::ApprovalForAll",
+            [owner, operator]
+        );
+
+        fn init_contract() -> Erc1155 {
+            // Alice owns 10 tokens of type 1 and 20 tokens of type 2.
+            let mut erc1155 = Erc1155::new();
+            assert_eq!(erc1155.mint(1, 10), Ok(()));
+            assert_eq!(erc1155.mint(2, 20), Ok(()));
+            erc1155
+        }
+
+        #[ink::test]
+        fn mint_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let erc1155 = init_contract();
+
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 10);
+            assert_eq!(erc1155.balance_of(accounts.alice, 2), 20);
+            assert_eq!(erc1155.balance_of(accounts.bob, 1), 0);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(2, emitted_events.len());
+            assert_event::<Event, _>(
+                &emitted_events[0],
+                TransferSingle {
+                    operator: accounts.alice,
+                    from: None,
+                    to: Some(accounts.alice),
+                    id: 1,
+                    value: 10,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn mint_overflow_fails() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = Erc1155::new();
+            assert_eq!(erc1155.mint(1, Balance::MAX), Ok(()));
+            assert_eq!(erc1155.mint(1, Balance::MAX), Err(Error::Overflow));
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), Balance::MAX);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(1, emitted_events.len());
+        }
+
+        #[ink::test]
+        fn transfer_overflow_fails() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = Erc1155::new();
+            assert_eq!(erc1155.mint(1, Balance::MAX), Ok(()));
+            assert_eq!(as_caller(accounts.bob, || erc1155.mint(1, 1)), Ok(()));
+
+            // Neither transfer moves any token.
+            as_caller(accounts.bob, || {
+                assert_eq!(
+                    erc1155.safe_transfer_from(
+                        accounts.bob,
+                        accounts.alice,
+                        1,
+                        1,
+                        vec![]
+                    ),
+                    Err(Error::Overflow)
+                );
+                assert_eq!(
+                    erc1155.safe_batch_transfer_from(
+                        accounts.bob,
+                        accounts.alice,
+                        vec![1],
+                        vec![1],
+                        vec![]
+                    ),
+                    Err(Error::Overflow)
+                );
+            });
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), Balance::MAX);
+            assert_eq!(erc1155.balance_of(accounts.bob, 1), 1);
+        }
+
+        #[ink::test]
+        fn balance_of_batch_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let erc1155 = init_contract();
+
+            assert_eq!(
+                erc1155.balance_of_batch(
+                    vec![accounts.alice, accounts.alice, accounts.bob],
+                    vec![1, 2, 1]
+                ),
+                Ok(vec![10, 20, 0])
+            );
+            assert_eq!(
+                erc1155.balance_of_batch(vec![accounts.alice], vec![1, 2]),
+                Err(Error::BatchTransferMismatch)
+            );
+        }
+
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn safe_transfer_from_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = init_contract();
+
+            assert_eq!(
+                erc1155.safe_transfer_from(accounts.alice, accounts.bob, 1, 5, vec![]),
+                Ok(())
+            );
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 5);
+            assert_eq!(erc1155.balance_of(accounts.bob, 1), 5);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(3, emitted_events.len());
+            assert_event::<Event, _>(
+                &emitted_events[2],
+                TransferSingle {
+                    operator: accounts.alice,
+                    from: Some(accounts.alice),
+                    to: Some(accounts.bob),
+                    id: 1,
+                    value: 5,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn invalid_transfer_should_fail() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = init_contract();
+
+            // Alice does not own enough tokens.
+            assert_eq!(
+                erc1155.safe_transfer_from(accounts.alice, accounts.bob, 1, 11, vec![]),
+                Err(Error::InsufficientBalance)
+            );
+            // Tokens cannot be sent to the zero account.
+            assert_eq!(
+                erc1155.safe_transfer_from(
+                    accounts.alice,
+                    AccountId::from([0x0; 32]),
+                    1,
+                    1,
+                    vec![]
+                ),
+                Err(Error::ZeroAddressTransfer)
+            );
+
+            // Bob is not approved to move Alice's tokens.
+            as_caller(accounts.bob, || {
+                assert_eq!(
+                    erc1155.safe_transfer_from(
+                        accounts.alice,
+                        accounts.bob,
+                        1,
+                        1,
+                        vec![]
+                    ),
+                    Err(Error::NotApproved)
+                )
+            });
+
+            // No more events must have been emitted
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 10);
+            assert_eq!(2, ink_env::test::recorded_events().count());
+        }
+
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn safe_transfer_to_receiver_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Django is a receiver contract accepting tokens, Eve one rejecting them.
+            ::contract::set_receiver(
+                &accounts.django,
+                Some(::contract::ON_ERC1155_RECEIVED),
+            );
+            ::contract::set_receiver(
+                &accounts.eve,
+                Some(::contract::ON_ERC1155_RECEIVED),
+            );
+            let mut erc1155 = init_contract();
+
+            assert_eq!(
+                erc1155.safe_transfer_from(accounts.alice, accounts.django, 1, 5, vec![]),
+                Ok(())
+            );
+            assert_eq!(erc1155.balance_of(accounts.django, 1), 5);
+            // Eve replies the magic value of a single transfer to a batch.
+            assert_eq!(
+                erc1155.safe_batch_transfer_from(
+                    accounts.alice,
+                    accounts.eve,
+                    vec![1, 2],
+                    vec![1, 1],
+                    vec![]
+                ),
+                Err(Error::TransferRejected)
+            );
+            assert_eq!(erc1155.balance_of(accounts.eve, 1), 0);
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 5);
+            assert_eq!(3, ink_env::test::recorded_events().count());
+        }
+
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn safe_batch_transfer_from_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = init_contract();
+
+            assert_eq!(
+                erc1155.safe_batch_transfer_from(
+                    accounts.alice,
+                    accounts.bob,
+                    vec![1, 2],
+                    vec![5, 10],
+                    vec![]
+                ),
+                Ok(())
+            );
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 5);
+            assert_eq!(erc1155.balance_of(accounts.alice, 2), 10);
+            assert_eq!(erc1155.balance_of(accounts.bob, 1), 5);
+            assert_eq!(erc1155.balance_of(accounts.bob, 2), 10);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(3, emitted_events.len());
+            assert_event::<Event, _>(
+                &emitted_events[2],
+                TransferBatch {
+                    operator: accounts.alice,
+                    from: Some(accounts.alice),
+                    to: Some(accounts.bob),
+                    ids: vec![1, 2],
+                    values: vec![5, 10],
+                },
+            );
+        }
+
+        #[ink::test]
+        fn invalid_batch_transfer_should_fail() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = init_contract();
+
+            // Lengths of `ids` and `values` differ.
+            assert_eq!(
+                erc1155.safe_batch_transfer_from(
+                    accounts.alice,
+                    accounts.bob,
+                    vec![1, 2],
+                    vec![5],
+                    vec![]
+                ),
+                Err(Error::BatchTransferMismatch)
+            );
+            // Token type 1 is repeated, together the values exceed Alice's balance.
+            assert_eq!(
+                erc1155.safe_batch_transfer_from(
+                    accounts.alice,
+                    accounts.bob,
+                    vec![2, 1, 1],
+                    vec![5, 6, 6],
+                    vec![]
+                ),
+                Err(Error::InsufficientBalance)
+            );
+
+            // Nothing changed and no more events must have been emitted
+            assert_eq!(erc1155.balance_of(accounts.alice, 1), 10);
+            assert_eq!(erc1155.balance_of(accounts.alice, 2), 20);
+            assert_eq!(erc1155.balance_of(accounts.bob, 2), 0);
+            assert_eq!(2, ink_env::test::recorded_events().count());
+        }
+
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn operator_transfer_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc1155 = init_contract();
+
+            // Alice cannot approve herself.
+            assert_eq!(
+                erc1155.set_approval_for_all(accounts.alice, true),
+                Err(Error::SelfApproval)
+            );
+            // Alice approves Bob as operator.
+            assert_eq!(erc1155.set_approval_for_all(accounts.bob, true), Ok(()));
+            assert!(erc1155.is_approved_for_all(accounts.alice, accounts.bob));
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            assert_event::<Event, _>(
+                event,
+                ApprovalForAll {
+                    owner: accounts.alice,
+                    operator: accounts.bob,
+                    approved: true,
+                },
+            );
+
+            // Bob transfers tokens from Alice to Eve.
+            as_caller(accounts.bob, || {
+                assert_eq!(
+                    erc1155.safe_transfer_from(
+                        accounts.alice,
+                        accounts.eve,
+                        2,
+                        7,
+                        vec![]
+                    ),
+                    Ok(())
+                )
+            });
+            assert_eq!(erc1155.balance_of(accounts.alice, 2), 13);
+            assert_eq!(erc1155.balance_of(accounts.eve, 2), 7);
+
+            // Alice revokes Bob.
+            assert_eq!(erc1155.set_approval_for_all(accounts.bob, false), Ok(()));
+            assert!(!erc1155.is_approved_for_all(accounts.alice, accounts.bob));
+
+            as_caller(accounts.bob, || {
+                assert_eq!(
+                    erc1155.safe_transfer_from(
+                        accounts.alice,
+                        accounts.eve,
+                        2,
+                        1,
+                        vec![]
+                    ),
+                    Err(Error::NotApproved)
+                )
+            });
+        }
+    }
+}
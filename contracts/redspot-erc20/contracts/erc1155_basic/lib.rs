@@ -0,0 +1,287 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod module;
+
+pub use ::contract::{
+    Env,
+    EnvAccess,
+};
+
+pub use module::{
+    Data,
+    TokenId,
+};
+
+mod erc1155_basic {
+    use super::{
+        Data,
+        TokenId,
+    };
+    use ::contract::{
+        Env,
+        EnvAccess,
+        Module,
+        ReceiverAccess,
+        ON_ERC1155_BATCH_RECEIVED,
+        ON_ERC1155_RECEIVED,
+    };
+    use ::ink_prelude::vec::Vec;
+
+    /// The ERC-1155 error types.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if not enough balance to fulfill a request is available.
+        InsufficientBalance,
+        /// Returned if the caller is neither the owner nor an approved operator.
+        NotApproved,
+        /// Returned if tokens are sent to the zero account.
+        ZeroAddressTransfer,
+        /// Returned if an owner tries to approve itself as an operator.
+        SelfApproval,
+        /// Returned if the lengths of the batch arguments do not match or are empty.
+        BatchTransferMismatch,
+        /// Returned if the receiver contract does not accept the tokens.
+        TransferRejected,
+        /// Returned if minting or receiving tokens would overflow a balance.
+        Overflow,
+    }
+
+    /// The ERC-1155 result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    pub trait Erc1155EventEmit<E: Env>: EnvAccess<E> {
+        fn emit_event_transfer_single(
+            &mut self,
+            operator: E::AccountId,
+            from: Option<E::AccountId>,
+            to: Option<E::AccountId>,
+            id: TokenId,
+            value: E::Balance,
+        );
+
+        fn emit_event_transfer_batch(
+            &mut self,
+            operator: E::AccountId,
+            from: Option<E::AccountId>,
+            to: Option<E::AccountId>,
+            ids: Vec<TokenId>,
+            values: Vec<E::Balance>,
+        );
+
+        fn emit_event_approval_for_all(
+            &mut self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+            approved: bool,
+        );
+    }
+
+    pub trait Erc1155Impl<E: Env>: Module<Data<E>> + Erc1155EventEmit<E> {
+        // logics
+        fn balance_of_impl(&self, owner: E::AccountId, id: TokenId) -> E::Balance {
+            self.get_module().get_balance(id, owner)
+        }
+
+        fn balance_of_batch_impl(
+            &self,
+            owners: Vec<E::AccountId>,
+            ids: Vec<TokenId>,
+        ) -> Result<Vec<E::Balance>> {
+            if owners.len() != ids.len() {
+                return Err(Error::BatchTransferMismatch)
+            }
+            Ok(owners
+                .into_iter()
+                .zip(ids.into_iter())
+                .map(|(owner, id)| self.get_module().get_balance(id, owner))
+                .collect())
+        }
+
+        fn is_approved_for_all_impl(
+            &self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+        ) -> bool {
+            self.get_module().is_approved_for_all(owner, operator)
+        }
+
+        fn mint_impl(&mut self, id: TokenId, value: E::Balance) -> Result<()> {
+            let caller = Self::caller();
+            let balance = self
+                .get_module()
+                .get_balance(id, caller.clone())
+                .checked_add(&value)
+                .ok_or(Error::Overflow)?;
+            self.get_module_mut()
+                .balance_insert((id, caller.clone()), balance);
+            self.emit_event_transfer_single(
+                caller.clone(),
+                None,
+                Some(caller),
+                id,
+                value,
+            );
+            Ok(())
+        }
+
+        fn set_approval_for_all_impl(
+            &mut self,
+            operator: E::AccountId,
+            approved: bool,
+        ) -> Result<()> {
+            let caller = Self::caller();
+            if operator == caller {
+                return Err(Error::SelfApproval)
+            }
+            if approved {
+                self.get_module_mut()
+                    .operator_approval_insert((caller.clone(), operator.clone()), true);
+            } else {
+                self.get_module_mut()
+                    .operator_approval_remove(&(caller.clone(), operator.clone()));
+            }
+            self.emit_event_approval_for_all(caller, operator, approved);
+            Ok(())
+        }
+
+        fn safe_transfer_from_impl(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+            value: E::Balance,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            let caller = Self::caller();
+            self.ensure_transfer_allowed(caller.clone(), from.clone(), to.clone())?;
+            let from_balance = self.get_module().get_balance(id, from.clone());
+            if from_balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            // The recipient holds the tokens when its callback runs.
+            self.transfer_balance(from.clone(), to.clone(), id, value)?;
+            if !Self::check_on_received(
+                &to,
+                ON_ERC1155_RECEIVED,
+                (caller.clone(), from.clone(), id, value, data),
+            ) {
+                // An error does not revert the storage, so the tokens go back.
+                self.transfer_balance(to, from, id, value)?;
+                return Err(Error::TransferRejected)
+            }
+            self.emit_event_transfer_single(caller, Some(from), Some(to), id, value);
+            Ok(())
+        }
+
+        fn safe_batch_transfer_from_impl(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            ids: Vec<TokenId>,
+            values: Vec<E::Balance>,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            let caller = Self::caller();
+            self.ensure_transfer_allowed(caller.clone(), from.clone(), to.clone())?;
+            if ids.is_empty() || ids.len() != values.len() {
+                return Err(Error::BatchTransferMismatch)
+            }
+            // Check every balance up front, accounting for repeated ids, so a failing
+            // batch leaves the storage untouched.
+            for (n, id) in ids.iter().enumerate() {
+                if ids[..n].contains(id) {
+                    continue
+                }
+                let mut remaining = self.get_module().get_balance(*id, from.clone());
+                let mut received = self.get_module().get_balance(*id, to.clone());
+                for (other, value) in ids.iter().zip(values.iter()) {
+                    if other != id {
+                        continue
+                    }
+                    if remaining < *value {
+                        return Err(Error::InsufficientBalance)
+                    }
+                    remaining -= *value;
+                    if from != to {
+                        received = received.checked_add(value).ok_or(Error::Overflow)?;
+                    }
+                }
+            }
+            for (id, value) in ids.iter().zip(values.iter()) {
+                self.transfer_balance(from.clone(), to.clone(), *id, *value)?;
+            }
+            if !Self::check_on_received(
+                &to,
+                ON_ERC1155_BATCH_RECEIVED,
+                (caller.clone(), from.clone(), ids.clone(), values.clone(), data),
+            ) {
+                for (id, value) in ids.iter().zip(values.iter()) {
+                    self.transfer_balance(to.clone(), from.clone(), *id, *value)?;
+                }
+                return Err(Error::TransferRejected)
+            }
+            self.emit_event_transfer_batch(caller, Some(from), Some(to), ids, values);
+            Ok(())
+        }
+
+        /// Checks that `caller` may move tokens of `from` to `to`.
+        fn ensure_transfer_allowed(
+            &self,
+            caller: E::AccountId,
+            from: E::AccountId,
+            to: E::AccountId,
+        ) -> Result<()> {
+            if caller != from && !self.get_module().is_approved_for_all(from, caller) {
+                return Err(Error::NotApproved)
+            }
+            if to == E::AccountId::default() {
+                return Err(Error::ZeroAddressTransfer)
+            }
+            Ok(())
+        }
+
+        /// Moves `value` tokens of type `id`, the balance of `from` must be checked
+        /// before. Fails with `Overflow` before moving anything if the balance of `to`
+        /// would overflow.
+        fn transfer_balance(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+            value: E::Balance,
+        ) -> Result<()> {
+            if from == to {
+                return Ok(())
+            }
+            let to_balance = self
+                .get_module()
+                .get_balance(id, to.clone())
+                .checked_add(&value)
+                .ok_or(Error::Overflow)?;
+            let from_balance = self.get_module().get_balance(id, from.clone());
+            self.get_module_mut()
+                .balance_insert((id, from), from_balance - value);
+            self.get_module_mut().balance_insert((id, to), to_balance);
+            Ok(())
+        }
+    }
+
+    impl<E: Env, T: Module<Data<E>> + Erc1155EventEmit<E>> Erc1155Impl<E>
+        for T
+    {
+    }
+}
+
+pub use erc1155_basic::{
+    Erc1155EventEmit,
+    Erc1155Impl,
+    Error,
+    Result,
+};
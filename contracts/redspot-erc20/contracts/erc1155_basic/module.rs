@@ -0,0 +1,94 @@
+pub mod module {
+    pub use ::contract::Env;
+    use ::contract::{
+        Migrate,
+        Namespace,
+    };
+
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::{
+        collections::HashMap as StorageHashMap,
+        traits::{
+            SpreadLayout,
+        },
+    };
+
+    /// The id of a token type.
+    pub type TokenId = u128;
+
+    #[cfg_attr(feature = "std", derive(::ink_storage::traits::StorageLayout))]
+    #[derive(Debug, SpreadLayout)]
+    pub struct Data<E: Env> {
+        /// Mapping from token type and owner to number of owned token.
+        balances: StorageHashMap<(TokenId, E::AccountId), E::Balance>,
+        /// Mapping from owner to operator approvals.
+        operator_approvals: StorageHashMap<(E::AccountId, E::AccountId), bool>,
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn new() -> Self {
+            Self {
+                balances: StorageHashMap::new(),
+                operator_approvals: StorageHashMap::new(),
+            }
+        }
+    }
+
+    impl<E: Env> Namespace for Data<E> {
+        const NAMESPACE: &'static [u8] = b"erc1155_basic::Data";
+    }
+
+    impl<E: Env> Migrate for Data<E> {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, _from: u32) {}
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn get_balance(&self, id: TokenId, owner: E::AccountId) -> E::Balance {
+            self.balances
+                .get(&(id, owner))
+                .copied()
+                .unwrap_or(E::Balance::from(0 as u8))
+        }
+
+        pub fn is_approved_for_all(
+            &self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+        ) -> bool {
+            self.operator_approvals
+                .get(&(owner, operator))
+                .copied()
+                .unwrap_or(false)
+        }
+
+        pub fn balance_insert(
+            &mut self,
+            id_owner: (TokenId, E::AccountId),
+            value: E::Balance,
+        ) {
+            self.balances.insert(id_owner, value);
+        }
+
+        pub fn operator_approval_insert(
+            &mut self,
+            owner_operator: (E::AccountId, E::AccountId),
+            approved: bool,
+        ) {
+            self.operator_approvals.insert(owner_operator, approved);
+        }
+
+        pub fn operator_approval_remove(
+            &mut self,
+            owner_operator: &(E::AccountId, E::AccountId),
+        ) {
+            self.operator_approvals.take(owner_operator);
+        }
+    }
+}
+
+pub use module::{
+    Data,
+    TokenId,
+};
@@ -0,0 +1,145 @@
+pub mod holders {
+    use crate::Data;
+    use ::contract::{
+        Env,
+        Migrate,
+        Module,
+        Namespace,
+    };
+    use ::ink_prelude::vec::Vec;
+
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::{
+        collections::{
+            HashMap as StorageHashMap,
+            Vec as StorageVec,
+        },
+        traits::SpreadLayout,
+    };
+
+    /// The most holders returned by one call of `holders_impl`, so that a page
+    /// stays within the gas and the output size of a message.
+    pub const MAX_HOLDERS_PAGE: u32 = 100;
+
+    /// Enumerable index of the accounts with a non-zero balance.
+    #[cfg_attr(feature = "std", derive(::ink_storage::traits::StorageLayout))]
+    #[derive(Debug, SpreadLayout)]
+    pub struct HoldersData<E: Env> {
+        /// The holders, in no particular order.
+        holders: StorageVec<E::AccountId>,
+        /// Mapping from holder to its index in `holders`.
+        indices: StorageHashMap<E::AccountId, u32>,
+    }
+
+    impl<E: Env> HoldersData<E> {
+        pub fn new() -> Self {
+            Self {
+                holders: StorageVec::new(),
+                indices: StorageHashMap::new(),
+            }
+        }
+    }
+
+    impl<E: Env> Namespace for HoldersData<E> {
+        const NAMESPACE: &'static [u8] = b"erc20_basic::HoldersData";
+    }
+
+    impl<E: Env> Migrate for HoldersData<E> {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, _from: u32) {}
+    }
+
+    impl<E: Env> HoldersData<E> {
+        pub fn len(&self) -> u32 {
+            self.holders.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.holders.is_empty()
+        }
+
+        pub fn contains(&self, holder: &E::AccountId) -> bool {
+            self.indices.contains_key(holder)
+        }
+
+        pub fn get(&self, index: u32) -> Option<&E::AccountId> {
+            self.holders.get(index)
+        }
+
+        /// Adds `holder` at the end, does nothing if it is already a holder.
+        pub fn insert(&mut self, holder: E::AccountId) {
+            if self.contains(&holder) {
+                return
+            }
+            self.indices.insert(holder.clone(), self.holders.len());
+            self.holders.push(holder);
+        }
+
+        /// Removes `holder` by moving the last holder to its index, does nothing if
+        /// it is not a holder.
+        pub fn remove(&mut self, holder: &E::AccountId) {
+            let index = match self.indices.take(holder) {
+                Some(index) => index,
+                None => return,
+            };
+            let last = self
+                .holders
+                .pop()
+                .expect("a holder is indexed, so there is a last holder");
+            if index < self.holders.len() {
+                *self
+                    .holders
+                    .get_mut(index)
+                    .expect("index is less than the length") = last.clone();
+                self.indices.insert(last, index);
+            }
+        }
+    }
+
+    /// Keeps `HoldersData` in sync with the balances of `Data`.
+    ///
+    /// Contracts using the extension call `update_holders_impl` from
+    /// `Erc20EventEmit::after_token_transfer`.
+    pub trait HoldersImpl<E: Env>: Module<Data<E>> + Module<HoldersData<E>> {
+        fn update_holders_impl(
+            &mut self,
+            from: Option<E::AccountId>,
+            to: Option<E::AccountId>,
+        ) {
+            for account in from.into_iter().chain(to) {
+                let balance = <Self as Module<Data<E>>>::get_module(self)
+                    .get_balance(account.clone());
+                let holders = <Self as Module<HoldersData<E>>>::get_module_mut(self);
+                if balance == E::Balance::from(0 as u8) {
+                    holders.remove(&account);
+                } else {
+                    holders.insert(account);
+                }
+            }
+        }
+
+        fn holders_count_impl(&self) -> u32 {
+            <Self as Module<HoldersData<E>>>::get_module(self).len()
+        }
+
+        /// Returns at most `limit` holders from the index `start`, and never more
+        /// than `MAX_HOLDERS_PAGE`.
+        fn holders_impl(&self, start: u32, limit: u32) -> Vec<E::AccountId> {
+            let holders = <Self as Module<HoldersData<E>>>::get_module(self);
+            let limit = limit.min(MAX_HOLDERS_PAGE);
+            let end = start.saturating_add(limit).min(holders.len());
+            (start..end)
+                .filter_map(|index| holders.get(index).cloned())
+                .collect()
+        }
+    }
+
+    impl<E: Env, T: Module<Data<E>> + Module<HoldersData<E>>> HoldersImpl<E> for T {}
+}
+
+pub use holders::{
+    HoldersData,
+    HoldersImpl,
+    MAX_HOLDERS_PAGE,
+};
@@ -0,0 +1,269 @@
+pub mod interface {
+    use crate::{
+        selectors::*,
+        Result,
+    };
+    use ::ink_env::{
+        call::FromAccountId,
+        Environment,
+    };
+
+    /// The result of a call to a token, an error if the call itself failed, e.g. the
+    /// token trapped or its reply could not be decoded.
+    pub type CallResult<T> = core::result::Result<T, ::ink_env::Error>;
+
+    /// The standard ERC-20 messages, as seen by the caller of a token.
+    pub trait Erc20Interface<T: Environment> {
+        fn total_supply(&self) -> CallResult<T::Balance>;
+
+        fn balance_of(&self, owner: T::AccountId) -> CallResult<T::Balance>;
+
+        fn allowance(
+            &self,
+            owner: T::AccountId,
+            spender: T::AccountId,
+        ) -> CallResult<T::Balance>;
+
+        fn transfer(
+            &mut self,
+            to: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>>;
+
+        fn approve(
+            &mut self,
+            spender: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>>;
+
+        fn transfer_from(
+            &mut self,
+            from: T::AccountId,
+            to: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>>;
+    }
+
+    /// A reference to any token contract implementing the standard ERC-20 selectors,
+    /// e.g. `Erc20` or `Erc20Ownable`.
+    pub struct Erc20Ref<T: Environment> {
+        account_id: T::AccountId,
+    }
+
+    impl<T: Environment> Erc20Ref<T> {
+        pub fn from_account_id(account_id: T::AccountId) -> Self {
+            Self { account_id }
+        }
+
+        pub fn account_id(&self) -> T::AccountId {
+            self.account_id.clone()
+        }
+
+        /// Calls `selector` on the token with `args`, without transferring value.
+        fn invoke<Args: scale::Encode, R: scale::Decode>(
+            &self,
+            selector: [u8; 4],
+            args: Args,
+        ) -> CallResult<R> {
+            let value = 0u32.into();
+            ::contract::invoke::<T, Args, R>(&self.account_id, selector, args, value)
+        }
+    }
+
+    impl<T: Environment> FromAccountId<T> for Erc20Ref<T> {
+        fn from_account_id(account_id: T::AccountId) -> Self {
+            Self { account_id }
+        }
+    }
+
+    impl<T: Environment> Erc20Interface<T> for Erc20Ref<T> {
+        fn total_supply(&self) -> CallResult<T::Balance> {
+            self.invoke(TOTAL_SUPPLY, ())
+        }
+
+        fn balance_of(&self, owner: T::AccountId) -> CallResult<T::Balance> {
+            self.invoke(BALANCE_OF, owner)
+        }
+
+        fn allowance(
+            &self,
+            owner: T::AccountId,
+            spender: T::AccountId,
+        ) -> CallResult<T::Balance> {
+            self.invoke(ALLOWANCE, (owner, spender))
+        }
+
+        fn transfer(
+            &mut self,
+            to: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>> {
+            self.invoke(TRANSFER, (to, value))
+        }
+
+        fn approve(
+            &mut self,
+            spender: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>> {
+            self.invoke(APPROVE, (spender, value))
+        }
+
+        fn transfer_from(
+            &mut self,
+            from: T::AccountId,
+            to: T::AccountId,
+            value: T::Balance,
+        ) -> CallResult<Result<()>> {
+            self.invoke(TRANSFER_FROM, (from, to, value))
+        }
+    }
+
+    #[cfg(all(test, feature = "test-utils"))]
+    mod tests {
+        use super::*;
+        use crate::Error;
+        use ::ink_env::DefaultEnvironment;
+        use scale::Encode;
+        use std::{
+            cell::RefCell,
+            rc::Rc,
+            vec::Vec,
+        };
+
+        type AccountId = <DefaultEnvironment as Environment>::AccountId;
+        type Balance = <DefaultEnvironment as Environment>::Balance;
+
+        /// The encoded callee, transferred value and input of a routed call.
+        type Call = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+        /// Routes the calls to a token replying `reply`, and returns the calls made.
+        fn route_to_token<R: Encode>(reply: R) -> Rc<RefCell<Vec<Call>>> {
+            let calls = Rc::new(RefCell::new(Vec::new()));
+            let recorded = calls.clone();
+            let reply = reply.encode();
+            ::contract::set_router(move |callee, value, input| {
+                recorded
+                    .borrow_mut()
+                    .push((callee.to_vec(), value.to_vec(), input));
+                Ok(reply.clone())
+            });
+            calls
+        }
+
+        /// Checks that the only call made invokes `selector` on `token` with `args`
+        /// and without value.
+        fn assert_call<Args: Encode>(
+            calls: &Rc<RefCell<Vec<Call>>>,
+            token: AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) {
+            let mut input = selector.to_vec();
+            args.encode_to(&mut input);
+            assert_eq!(
+                *calls.borrow(),
+                vec![(token.encode(), (0 as Balance).encode(), input)]
+            );
+        }
+
+        fn accounts() -> (AccountId, AccountId, AccountId) {
+            (
+                AccountId::from([0x07; 32]),
+                AccountId::from([0x01; 32]),
+                AccountId::from([0x02; 32]),
+            )
+        }
+
+        #[test]
+        fn total_supply_encodes_call() {
+            let (token, _, _) = accounts();
+            let calls = route_to_token(100 as Balance);
+            let erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(erc20.total_supply(), Ok(100));
+            assert_call(&calls, token, TOTAL_SUPPLY, ());
+        }
+
+        #[test]
+        fn balance_of_encodes_call() {
+            let (token, alice, _) = accounts();
+            let calls = route_to_token(10 as Balance);
+            let erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(erc20.balance_of(alice), Ok(10));
+            assert_call(&calls, token, BALANCE_OF, alice);
+        }
+
+        #[test]
+        fn allowance_encodes_call() {
+            let (token, alice, bob) = accounts();
+            let calls = route_to_token(5 as Balance);
+            let erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(erc20.allowance(alice, bob), Ok(5));
+            assert_call(&calls, token, ALLOWANCE, (alice, bob));
+        }
+
+        #[test]
+        fn transfer_encodes_call() {
+            let (token, _, bob) = accounts();
+            let calls = route_to_token(Err::<(), _>(Error::InsufficientBalance));
+            let mut erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(
+                erc20.transfer(bob, 10),
+                Ok(Err(Error::InsufficientBalance))
+            );
+            assert_call(&calls, token, TRANSFER, (bob, 10 as Balance));
+        }
+
+        #[test]
+        fn approve_encodes_call() {
+            let (token, _, bob) = accounts();
+            let calls = route_to_token(Ok::<(), Error>(()));
+            let mut erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(erc20.approve(bob, 10), Ok(Ok(())));
+            assert_call(&calls, token, APPROVE, (bob, 10 as Balance));
+        }
+
+        #[test]
+        fn transfer_from_encodes_call() {
+            let (token, alice, bob) = accounts();
+            let calls = route_to_token(Ok::<(), Error>(()));
+            let mut erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert_eq!(erc20.transfer_from(alice, bob, 10), Ok(Ok(())));
+            assert_call(&calls, token, TRANSFER_FROM, (alice, bob, 10 as Balance));
+        }
+
+        #[test]
+        fn undecodable_reply_fails() {
+            let (token, alice, _) = accounts();
+            route_to_token(0u8);
+            let erc20 = Erc20Ref::<DefaultEnvironment>::from_account_id(token);
+            assert!(matches!(
+                erc20.balance_of(alice),
+                Err(::ink_env::Error::Decode(_))
+            ));
+        }
+
+        #[test]
+        fn selectors_are_distinct() {
+            let selectors = [
+                NEW,
+                NEW_WITH_METADATA,
+                TOTAL_SUPPLY,
+                BALANCE_OF,
+                ALLOWANCE,
+                TRANSFER,
+                APPROVE,
+                TRANSFER_FROM,
+            ];
+            for (n, selector) in selectors.iter().enumerate() {
+                assert!(!selectors[..n].contains(selector));
+            }
+        }
+    }
+}
+
+pub use interface::{
+    CallResult,
+    Erc20Interface,
+    Erc20Ref,
+};
@@ -1,5 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod holders;
+mod interface;
+#[cfg(feature = "test-utils")]
+mod mock;
 mod module;
 
 pub use ::contract::{
@@ -7,15 +11,54 @@ pub use ::contract::{
     EnvAccess,
 };
 
+pub use holders::{
+    HoldersData,
+    HoldersImpl,
+    MAX_HOLDERS_PAGE,
+};
+pub use interface::{
+    Erc20Interface,
+    Erc20Ref,
+};
+#[cfg(feature = "test-utils")]
+pub use mock::MockErc20;
 pub use module::Data;
 
+/// Selectors of the standard ERC-20 messages.
+///
+/// Token contracts fix their messages to these selectors, so that `Erc20Ref` can
+/// call any of them and a rename does not break the callers.
+pub mod selectors {
+    /// Selector of the constructor `new(initial_supply)`.
+    pub const NEW: [u8; 4] = [0x9b, 0xae, 0x9d, 0x5e];
+    /// Selector of the constructor
+    /// `new_with_metadata(name, symbol, decimals, initial_supply, owner)`.
+    pub const NEW_WITH_METADATA: [u8; 4] = [0xc6, 0x5b, 0xc7, 0x26];
+    /// Selector of `total_supply()`.
+    pub const TOTAL_SUPPLY: [u8; 4] = [0xdb, 0x63, 0x75, 0xa8];
+    /// Selector of `balance_of(owner)`.
+    pub const BALANCE_OF: [u8; 4] = [0x0f, 0x75, 0x5a, 0x56];
+    /// Selector of `allowance(owner, spender)`.
+    pub const ALLOWANCE: [u8; 4] = [0x6a, 0x00, 0x16, 0x5e];
+    /// Selector of `transfer(to, value)`.
+    pub const TRANSFER: [u8; 4] = [0x84, 0xa1, 0x5d, 0xa1];
+    /// Selector of `approve(spender, value)`.
+    pub const APPROVE: [u8; 4] = [0x68, 0x12, 0x66, 0xa0];
+    /// Selector of `transfer_from(from, to, value)`.
+    pub const TRANSFER_FROM: [u8; 4] = [0x0b, 0x39, 0x6f, 0x18];
+}
+
 mod erc20_basic {
     use super::Data;
     use ::contract::{
         Env,
         EnvAccess,
         Module,
+        ReceiverAccess,
+        ON_ERC20_RECEIVED,
     };
+    use ::ink_prelude::vec::Vec;
+    use num_traits::CheckedAdd;
 
     /// The ERC-20 error types.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -25,6 +68,10 @@ mod erc20_basic {
         InsufficientBalance,
         /// Returned if not enough allowance to fulfill a request is available.
         InsufficientAllowance,
+        /// Returned if the receiver contract does not accept the tokens.
+        TransferRejected,
+        /// Returned if minting would overflow the total supply or a balance.
+        Overflow,
     }
 
     /// The ERC-20 result type.
@@ -44,6 +91,18 @@ mod erc20_basic {
             spender: E::AccountId,
             value: E::Balance,
         );
+
+        /// Hook of the contract into the token logic, called after `value` tokens
+        /// moved `from` an account `to` another, `None` for minted and burned tokens.
+        ///
+        /// Defaults to nothing, so contracts without hooks need not implement it.
+        fn after_token_transfer(
+            &mut self,
+            _from: Option<E::AccountId>,
+            _to: Option<E::AccountId>,
+            _value: E::Balance,
+        ) {
+        }
     }
 
     pub trait Erc20Impl<E: Env>: Module<Data<E>> + Erc20EventEmit<E> {
@@ -53,6 +112,7 @@ mod erc20_basic {
             self.get_module_mut().set_total_supply(initial_supply);
             self.get_module_mut()
                 .balance_insert(caller.clone(), initial_supply);
+            self.after_token_transfer(None, Some(caller.clone()), initial_supply);
 
             self.emit_event_transfer(None, Some(caller), initial_supply);
         }
@@ -91,32 +151,151 @@ mod erc20_basic {
             Ok(())
         }
 
+        /// Transfers like `transfer_impl`, a contract recipient must accept the tokens
+        /// in its `on_erc20_received` callback.
+        fn safe_transfer_impl(
+            &mut self,
+            to: E::AccountId,
+            value: E::Balance,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            let from = Self::caller();
+            self.safe_move_impl(from.clone(), from, to, value, data)
+        }
+
+        /// Transfers like `transfer_from_impl`, a contract recipient must accept the
+        /// tokens in its `on_erc20_received` callback.
+        fn safe_transfer_from_impl(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            value: E::Balance,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            let caller = Self::caller();
+            let allowance = self
+                .get_module()
+                .get_allowance(from.clone(), caller.clone());
+            if allowance < value {
+                return Err(Error::InsufficientAllowance)
+            }
+            self.safe_move_impl(caller.clone(), from.clone(), to, value, data)?;
+            self.get_module_mut()
+                .allowance_insert((from, caller), allowance - value);
+            Ok(())
+        }
+
         fn transfer_from_to_impl(
             &mut self,
             from: E::AccountId,
             to: E::AccountId,
             value: E::Balance,
+        ) -> Result<()> {
+            self.move_balance(from.clone(), to.clone(), value)?;
+            self.after_token_transfer(Some(from.clone()), Some(to.clone()), value);
+
+            self.emit_event_transfer(Some(from), Some(to), value);
+
+            Ok(())
+        }
+
+        /// Moves `value` tokens `from` an account `to` another, without the hook and
+        /// the event of a transfer.
+        fn move_balance(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            value: E::Balance,
         ) -> Result<()> {
             let from_balance = self.get_module().get_balance(from.clone());
             if from_balance < value {
                 return Err(Error::InsufficientBalance)
             }
             self.get_module_mut()
-                .balance_insert(from.clone(), from_balance - value);
+                .balance_insert(from, from_balance - value);
             let to_balance = self.get_module().get_balance(to.clone());
-            self.get_module_mut()
-                .balance_insert(to.clone(), to_balance + value);
+            self.get_module_mut().balance_insert(to, to_balance + value);
+            Ok(())
+        }
+
+        /// Moves `value` tokens `from` an account `to` a recipient that must accept
+        /// them in its `on_erc20_received` callback, called by `operator`.
+        fn safe_move_impl(
+            &mut self,
+            operator: E::AccountId,
+            from: E::AccountId,
+            to: E::AccountId,
+            value: E::Balance,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            // The recipient holds the tokens when its callback runs.
+            self.move_balance(from.clone(), to.clone(), value)?;
+            if !Self::check_on_received(
+                &to,
+                ON_ERC20_RECEIVED,
+                (operator, from.clone(), value, data),
+            ) {
+                // An error does not revert the storage, so the tokens go back.
+                self.move_balance(to, from, value)?;
+                return Err(Error::TransferRejected)
+            }
+            self.after_token_transfer(Some(from.clone()), Some(to.clone()), value);
 
             self.emit_event_transfer(Some(from), Some(to), value);
 
             Ok(())
         }
-    }
 
-    impl<E: Env, T: Module<Data<E>> + Erc20EventEmit<E>> Erc20Impl<E>
-        for T
-    {
+        /// Creates `value` tokens for `to`, increasing the total supply.
+        fn mint_impl(&mut self, to: E::AccountId, value: E::Balance) -> Result<()> {
+            let total_supply = self
+                .get_module()
+                .get_total_supply()
+                .checked_add(&value)
+                .ok_or(Error::Overflow)?;
+            // No balance exceeds the total supply, the check is kept for safety.
+            let to_balance = self
+                .get_module()
+                .get_balance(to.clone())
+                .checked_add(&value)
+                .ok_or(Error::Overflow)?;
+            self.get_module_mut().set_total_supply(total_supply);
+            self.get_module_mut().balance_insert(to.clone(), to_balance);
+            self.after_token_transfer(None, Some(to.clone()), value);
+
+            self.emit_event_transfer(None, Some(to), value);
+
+            Ok(())
+        }
+
+        /// Destroys `value` tokens of `from`, decreasing the total supply.
+        fn burn_impl(&mut self, from: E::AccountId, value: E::Balance) -> Result<()> {
+            let from_balance = self.get_module().get_balance(from.clone());
+            if from_balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            self.get_module_mut()
+                .balance_insert(from.clone(), from_balance - value);
+            let total_supply = self.get_module().get_total_supply();
+            self.get_module_mut().set_total_supply(total_supply - value);
+            self.after_token_transfer(Some(from.clone()), None, value);
+
+            self.emit_event_transfer(Some(from), None, value);
+
+            Ok(())
+        }
     }
+
+    impl<E: Env, T: Module<Data<E>> + Erc20EventEmit<E>> Erc20Impl<E> for T {}
 }
 
 pub use erc20_basic::{
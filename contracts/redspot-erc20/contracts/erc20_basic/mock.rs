@@ -0,0 +1,136 @@
+pub mod mock {
+    use crate::{
+        Data,
+        Erc20EventEmit,
+    };
+    use ::contract::{
+        Env,
+        MockContract,
+        MockEnv,
+        MockEvent,
+    };
+
+    type AccountId = <MockEnv as Env>::AccountId;
+    type Balance = <MockEnv as Env>::Balance;
+
+    /// A token made of the `erc20_basic` module alone.
+    pub type MockErc20 = MockContract<Data<MockEnv>>;
+
+    impl Erc20EventEmit<MockEnv> for MockErc20 {
+        fn emit_event_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
+        ) {
+            MockEnv::record_event(MockEvent::new("Transfer", (from, to, value)));
+        }
+
+        fn emit_event_approval(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) {
+            MockEnv::record_event(MockEvent::new("Approval", (owner, spender, value)));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{
+            Erc20Impl,
+            Error,
+        };
+        use ::contract::{
+            EnvAccess,
+            Module,
+        };
+
+        fn new_token(initial_supply: Balance) -> MockErc20 {
+            let mut token = MockErc20::new(Data::new());
+            token.new_impl(initial_supply);
+            token
+        }
+
+        #[test]
+        fn new_impl_works() {
+            MockEnv::run(|| {
+                let token = new_token(100);
+                let alice = MockEnv::caller();
+                assert_eq!(token.get_module().get_total_supply(), 100);
+                assert_eq!(token.get_module().get_balance(alice), 100);
+                assert_eq!(
+                    MockEnv::events(),
+                    vec![MockEvent::new(
+                        "Transfer",
+                        (None::<AccountId>, Some(alice), 100 as Balance)
+                    )]
+                );
+            })
+        }
+
+        #[test]
+        fn transfer_from_impl_works() {
+            MockEnv::run(|| {
+                let alice = AccountId::from([0x01; 32]);
+                let bob = AccountId::from([0x02; 32]);
+                let eve = AccountId::from([0x05; 32]);
+                let mut token = new_token(100);
+
+                assert_eq!(token.approve_impl(bob, 10), Ok(()));
+                MockEnv::set_caller(bob);
+                assert_eq!(
+                    token.transfer_from_impl(alice, eve, 20),
+                    Err(Error::InsufficientAllowance)
+                );
+                assert_eq!(token.transfer_from_impl(alice, eve, 10), Ok(()));
+                assert_eq!(token.get_module().get_balance(eve), 10);
+                assert_eq!(token.get_module().get_allowance(alice, bob), 0);
+                assert_eq!(
+                    MockEnv::events()[1..],
+                    [
+                        MockEvent::new("Approval", (alice, bob, 10 as Balance)),
+                        MockEvent::new("Transfer", (Some(alice), Some(eve), 10 as Balance)),
+                    ]
+                );
+            })
+        }
+
+        #[test]
+        fn mint_and_burn_impl_work() {
+            MockEnv::run(|| {
+                let alice = MockEnv::caller();
+                let mut token = new_token(100);
+                assert_eq!(token.mint_impl(alice, 50), Ok(()));
+                assert_eq!(
+                    token.burn_impl(alice, 200),
+                    Err(Error::InsufficientBalance)
+                );
+                assert_eq!(token.burn_impl(alice, 30), Ok(()));
+                assert_eq!(token.get_module().get_total_supply(), 120);
+                assert_eq!(token.get_module().get_balance(alice), 120);
+            })
+        }
+
+        #[test]
+        fn mint_impl_overflow_fails() {
+            MockEnv::run(|| {
+                let alice = MockEnv::caller();
+                let mut token = new_token(100);
+                assert_eq!(
+                    token.mint_impl(alice, Balance::MAX - 99),
+                    Err(Error::Overflow)
+                );
+                assert_eq!(token.mint_impl(alice, Balance::MAX - 100), Ok(()));
+                assert_eq!(token.get_module().get_total_supply(), Balance::MAX);
+                assert_eq!(token.mint_impl(alice, 1), Err(Error::Overflow));
+                assert_eq!(token.get_module().get_balance(alice), Balance::MAX);
+                assert_eq!(MockEnv::events().len(), 2);
+            })
+        }
+    }
+}
+
+pub use mock::MockErc20;
@@ -1,5 +1,9 @@
 pub mod module {
     pub use ::contract::Env;
+    use ::contract::{
+        Migrate,
+        Namespace,
+    };
 
     #[cfg(not(feature = "ink-as-dependency"))]
     use ::ink_storage::{
@@ -32,6 +36,16 @@ pub mod module {
         }
     }
 
+    impl<E: Env> Namespace for Data<E> {
+        const NAMESPACE: &'static [u8] = b"erc20_basic::Data";
+    }
+
+    impl<E: Env> Migrate for Data<E> {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, _from: u32) {}
+    }
+
     impl<E: Env> Data<E> {
         pub fn get_balance(&self, owner: E::AccountId) -> E::Balance {
             self.balances
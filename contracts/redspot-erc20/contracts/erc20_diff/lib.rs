@@ -0,0 +1,83 @@
+//! Differential tests of the two ERC-20 contracts, the modular `erc20::Erc20` and
+//! the monolithic `erc20owner::Erc20Ownable`.
+//!
+//! Both contracts run the same calls, each in a fresh off-chain environment, and
+//! must agree on every result, balance, allowance and emitted event.
+
+#[cfg(test)]
+mod tests {
+    use ::contract_test::{
+        assert_same_erc20_ops,
+        erc20_contract,
+        erc20_ops,
+        Erc20Op,
+    };
+    use ::erc20::erc20::Erc20;
+    use ::erc20owner::erc20owner::{
+        Erc20Ownable,
+        Error as Erc20OwnableError,
+    };
+    use proptest::prelude::*;
+
+    erc20_contract!(ModularErc20(Erc20), ::erc20_basic::Error);
+    erc20_contract!(MonolithicErc20(Erc20Ownable), Erc20OwnableError);
+
+    #[test]
+    fn edge_cases_agree() {
+        use Erc20Op::*;
+
+        assert_same_erc20_ops::<ModularErc20, MonolithicErc20>(&[
+            // To oneself, then more than the balance.
+            Transfer {
+                caller: 0,
+                to: 0,
+                value: 10,
+            },
+            Transfer {
+                caller: 1,
+                to: 0,
+                value: 1,
+            },
+            // An allowance is overwritten, and spent on a transfer to the owner.
+            Approve {
+                caller: 0,
+                spender: 1,
+                value: 30,
+            },
+            Approve {
+                caller: 0,
+                spender: 1,
+                value: 20,
+            },
+            TransferFrom {
+                caller: 1,
+                from: 0,
+                to: 0,
+                value: 20,
+            },
+            // Without allowance left, and from oneself without allowance.
+            TransferFrom {
+                caller: 1,
+                from: 0,
+                to: 2,
+                value: 1,
+            },
+            TransferFrom {
+                caller: 0,
+                from: 0,
+                to: 2,
+                value: 0,
+            },
+        ]);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(128))]
+
+        /// The contracts agree on random calls.
+        #[test]
+        fn random_calls_agree(ops in erc20_ops(32)) {
+            assert_same_erc20_ops::<ModularErc20, MonolithicErc20>(&ops);
+        }
+    }
+}
@@ -0,0 +1,611 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+pub mod erc721 {
+    use ::ink_lang::{
+        EmitEvent,
+        Env,
+        StaticEnv,
+    };
+    use ::ink_prelude::vec::Vec;
+
+    use ::erc721_basic::{
+        Data as Erc721Data,
+        Erc721EventEmit,
+        Erc721Impl,
+        Result,
+        TokenId,
+    };
+
+    /// A simple ERC-721 contract.
+    #[ink(storage)]
+    pub struct Erc721 {
+        data_erc721: ::contract::Namespaced<Erc721Data<Erc721>>,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when a token approve occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when an operator is enabled or disabled for an owner.
+    /// The operator can manage all NFTs of the owner.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    // TODO: Make by macro
+    use ::contract::Module;
+    impl ::contract::Env for Erc721 {
+        type AccountId = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::AccountId;
+        type Balance = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Balance;
+        type Hash = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Hash;
+        type Timestamp = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::Timestamp;
+        type BlockNumber = <::ink_env::DefaultEnvironment as ::ink_env::Environment>::BlockNumber;
+    }
+
+    impl ::contract::EnvAccess<Erc721> for Erc721 {
+        fn caller() -> <Erc721 as ::contract::Env>::AccountId {
+            Self::env().caller()
+        }
+
+        fn transferred_balance() -> <Erc721 as ::contract::Env>::Balance {
+            Self::env().transferred_balance()
+        }
+    }
+
+    impl ::contract::Module<Erc721Data<Erc721>> for Erc721 {
+        fn get_module(&self) -> &Erc721Data<Erc721> {
+            &self.data_erc721
+        }
+
+        fn get_module_mut(&mut self) -> &mut Erc721Data<Erc721> {
+            &mut self.data_erc721
+        }
+    }
+
+    impl ::contract::ReceiverAccess<Erc721> for Erc721 {
+        fn call_receiver<Args: scale::Encode>(
+            to: &AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> Result<[u8; 4], ::ink_env::Error> {
+            ::contract::call_receiver::<::ink_env::DefaultEnvironment, Args>(
+                to,
+                selector,
+                args,
+            )
+        }
+    }
+    // TODO: Make by macro
+
+    // TODO: Event in ink!
+    impl Erc721EventEmit<Erc721> for Erc721 {
+        fn emit_event_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            id: TokenId,
+        ) {
+            self.env().emit_event(Transfer { from, to, id });
+        }
+
+        fn emit_event_approval(&mut self, from: AccountId, to: AccountId, id: TokenId) {
+            self.env().emit_event(Approval { from, to, id });
+        }
+
+        fn emit_event_approval_for_all(
+            &mut self,
+            owner: AccountId,
+            operator: AccountId,
+            approved: bool,
+        ) {
+            self.env().emit_event(ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            });
+        }
+    }
+    // TODO: Event in ink!
+
+    // impl
+    impl Erc721 {
+        /// Creates a new ERC-721 token contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                data_erc721: ::contract::Namespaced::init(Erc721Data::new()),
+            }
+        }
+
+        /// Returns the balance of the owner.
+        ///
+        /// This represents the amount of unique tokens the owner has.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.balance_of_impl(owner)
+        }
+
+        /// Returns the owner of the token.
+        #[ink(message)]
+        pub fn owner_of(&self, id: TokenId) -> Option<AccountId> {
+            self.owner_of_impl(id)
+        }
+
+        /// Returns the approved account ID for this token if any.
+        #[ink(message)]
+        pub fn get_approved(&self, id: TokenId) -> Option<AccountId> {
+            self.get_approved_impl(id)
+        }
+
+        /// Returns `true` if the operator is approved by the owner.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.is_approved_for_all_impl(owner, operator)
+        }
+
+        /// Approves or disapproves the operator for all tokens of the caller.
+        #[ink(message)]
+        pub fn set_approval_for_all(
+            &mut self,
+            to: AccountId,
+            approved: bool,
+        ) -> Result<()> {
+            self.set_approval_for_all_impl(to, approved)
+        }
+
+        /// Approves the account to transfer the specified token on behalf of the caller.
+        #[ink(message)]
+        pub fn approve(&mut self, to: AccountId, id: TokenId) -> Result<()> {
+            self.approve_impl(to, id)
+        }
+
+        /// Transfers the token from the caller to the given destination.
+        #[ink(message)]
+        pub fn transfer(&mut self, destination: AccountId, id: TokenId) -> Result<()> {
+            self.transfer_impl(destination, id)
+        }
+
+        /// Transfer approved or owned token.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            self.transfer_from_impl(from, to, id)
+        }
+
+        /// Transfer approved or owned token, a contract `to` must accept it in its
+        /// `on_erc721_received` callback.
+        #[ink(message)]
+        pub fn safe_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: TokenId,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.safe_transfer_from_impl(from, to, id, data)
+        }
+
+        /// Creates a new token.
+        #[ink(message)]
+        pub fn mint(&mut self, id: TokenId) -> Result<()> {
+            self.mint_impl(id)
+        }
+
+        /// Deletes an existing token. Only the owner can burn the token.
+        #[ink(message)]
+        pub fn burn(&mut self, id: TokenId) -> Result<()> {
+            self.burn_impl(id)
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ::contract_test::{
+            as_caller,
+            assert_event,
+        };
+        use ::erc721_basic::Error;
+
+        type Event = <Erc721 as ::ink_lang::BaseEvent>::Type;
+
+        use ink_lang as ink;
+
+        ::contract_test::event_topics!(Transfer, "Erc721::Transfer", [from, to, id]);
+        ::contract_test::event_topics!(Approval, "Erc721::Approval", [from, to, id]);
+        ::contract_test::event_topics!(
+            ApprovalForAll,
+            "Erc721::ApprovalForAll",
+            [owner, operator]
+        );
+
+        fn assert_transfer_event(
+            event: &ink_env::test::EmittedEvent,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            id: TokenId,
+        ) {
+            assert_event::<Event, _>(event, Transfer { from, to, id });
+        }
+
+        #[ink::test]
+        fn mint_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Token 1 does not exists.
+            assert_eq!(erc721.owner_of(1), None);
+            // Alice does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.alice), 0);
+            // Create token Id 1.
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            assert_eq!(erc721.owner_of(1), Some(accounts.alice));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(1, emitted_events.len());
+            assert_transfer_event(&emitted_events[0], None, Some(accounts.alice), 1);
+        }
+
+        #[ink::test]
+        fn mint_existing_should_fail() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1.
+            assert_eq!(erc721.mint(1), Ok(()));
+            // The first Transfer event takes place
+            assert_eq!(1, ink_env::test::recorded_events().count());
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Alice owns token Id 1.
+            assert_eq!(erc721.owner_of(1), Some(accounts.alice));
+            // Cannot create token Id if it exists.
+            // Bob cannot own token Id 1.
+            assert_eq!(erc721.mint(1), Err(Error::TokenExists));
+            // No more events must have been emitted
+            assert_eq!(1, ink_env::test::recorded_events().count());
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1 for Alice
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Alice owns token 1
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Bob does not owns any token
+            assert_eq!(erc721.balance_of(accounts.bob), 0);
+            // Alice transfers token 1 to Bob
+            assert_eq!(erc721.transfer(accounts.bob, 1), Ok(()));
+            // Bob owns token 1
+            assert_eq!(erc721.balance_of(accounts.alice), 0);
+            assert_eq!(erc721.balance_of(accounts.bob), 1);
+            assert_eq!(erc721.owner_of(1), Some(accounts.bob));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(2, emitted_events.len());
+            assert_transfer_event(
+                &emitted_events[1],
+                Some(accounts.alice),
+                Some(accounts.bob),
+                1,
+            );
+        }
+
+        #[ink::test]
+        fn invalid_transfer_should_fail() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Transfer token fails if it does not exists.
+            assert_eq!(erc721.transfer(accounts.bob, 2), Err(Error::TokenNotFound));
+            // Token Id 2 does not exists.
+            assert_eq!(erc721.owner_of(2), None);
+            // Create token Id 2.
+            assert_eq!(erc721.mint(2), Ok(()));
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Token Id 2 is owned by Alice.
+            assert_eq!(erc721.owner_of(2), Some(accounts.alice));
+            // Bob cannot transfer not owned tokens.
+            as_caller(accounts.bob, || {
+                assert_eq!(erc721.transfer(accounts.eve, 2), Err(Error::NotApproved))
+            });
+            // Nothing changed and no more events must have been emitted
+            assert_eq!(erc721.owner_of(2), Some(accounts.alice));
+            assert_eq!(1, ink_env::test::recorded_events().count());
+        }
+
+        #[ink::test]
+        fn approved_transfer_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1.
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Token Id 1 is owned by Alice.
+            assert_eq!(erc721.owner_of(1), Some(accounts.alice));
+            // Approve token Id 1 transfer for Bob on behalf of Alice.
+            assert_eq!(erc721.approve(accounts.bob, 1), Ok(()));
+            assert_eq!(erc721.get_approved(1), Some(accounts.bob));
+            // Bob transfers token Id 1 from Alice to Eve.
+            as_caller(accounts.bob, || {
+                assert_eq!(
+                    erc721.transfer_from(accounts.alice, accounts.eve, 1),
+                    Ok(())
+                )
+            });
+            // TokenId 1 is owned by Eve and the approval is cleared.
+            assert_eq!(erc721.owner_of(1), Some(accounts.eve));
+            assert_eq!(erc721.get_approved(1), None);
+            // Alice does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.alice), 0);
+            // Bob does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.bob), 0);
+            // Eve owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.eve), 1);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(3, emitted_events.len());
+            // The second event `emitted_events[1]` is an Approval event that we skip checking.
+            assert_transfer_event(
+                &emitted_events[2],
+                Some(accounts.alice),
+                Some(accounts.eve),
+                1,
+            );
+        }
+
+        #[ink::test]
+        fn approved_for_all_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1 and 2.
+            assert_eq!(erc721.mint(1), Ok(()));
+            assert_eq!(erc721.mint(2), Ok(()));
+            // Alice owns 2 tokens.
+            assert_eq!(erc721.balance_of(accounts.alice), 2);
+            // Approve token Id 1 transfer for Bob on behalf of Alice.
+            assert_eq!(erc721.set_approval_for_all(accounts.bob, true), Ok(()));
+            // Bob is an approved operator for Alice
+            assert!(erc721.is_approved_for_all(accounts.alice, accounts.bob));
+            as_caller(accounts.bob, || {
+                // Bob transfers token Id 1 from Alice to Eve.
+                assert_eq!(
+                    erc721.transfer_from(accounts.alice, accounts.eve, 1),
+                    Ok(())
+                );
+                // TokenId 1 is owned by Eve.
+                assert_eq!(erc721.owner_of(1), Some(accounts.eve));
+                // Alice owns 1 token.
+                assert_eq!(erc721.balance_of(accounts.alice), 1);
+                // Bob transfers token Id 2 from Alice to Eve.
+                assert_eq!(
+                    erc721.transfer_from(accounts.alice, accounts.eve, 2),
+                    Ok(())
+                );
+            });
+            // Bob does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.bob), 0);
+            // Eve owns 2 tokens.
+            assert_eq!(erc721.balance_of(accounts.eve), 2);
+            // Remove operator approval for Bob on behalf of Alice.
+            assert_eq!(erc721.set_approval_for_all(accounts.bob, false), Ok(()));
+            // Bob is not an approved operator for Alice.
+            assert!(!erc721.is_approved_for_all(accounts.alice, accounts.bob));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            assert_event::<Event, _>(
+                event,
+                ApprovalForAll {
+                    owner: accounts.alice,
+                    operator: accounts.bob,
+                    approved: false,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn operator_approval_names_owner() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut erc721 = Erc721::new();
+            assert_eq!(erc721.mint(1), Ok(()));
+            assert_eq!(erc721.set_approval_for_all(accounts.bob, true), Ok(()));
+            // Bob approves Eve for the token of Alice.
+            as_caller(accounts.bob, || {
+                assert_eq!(erc721.approve(accounts.eve, 1), Ok(()))
+            });
+            assert_eq!(erc721.get_approved(1), Some(accounts.eve));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            assert_event::<Event, _>(
+                event,
+                Approval {
+                    from: accounts.alice,
+                    to: accounts.eve,
+                    id: 1,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn not_approved_transfer_should_fail() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1.
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Bob does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.bob), 0);
+            // Eve does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.eve), 0);
+            // Eve is not an approved operator by Alice.
+            as_caller(accounts.eve, || {
+                assert_eq!(
+                    erc721.transfer_from(accounts.alice, accounts.frank, 1),
+                    Err(Error::NotApproved)
+                )
+            });
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Bob does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.bob), 0);
+            // Eve does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.eve), 0);
+        }
+
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn safe_transfer_from_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Django is a receiver contract accepting tokens, Eve one failing the call.
+            ::contract::set_receiver(
+                &accounts.django,
+                Some(::contract::ON_ERC721_RECEIVED),
+            );
+            ::contract::set_receiver(&accounts.eve, None);
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1 and 2 for Alice
+            assert_eq!(erc721.mint(1), Ok(()));
+            assert_eq!(erc721.mint(2), Ok(()));
+            // Plain accounts can always receive tokens.
+            assert_eq!(
+                erc721.safe_transfer_from(accounts.alice, accounts.bob, 1, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(erc721.owner_of(1), Some(accounts.bob));
+            // Eve does not accept the token.
+            assert_eq!(
+                erc721.safe_transfer_from(accounts.alice, accounts.eve, 2, Vec::new()),
+                Err(Error::TransferRejected)
+            );
+            assert_eq!(erc721.owner_of(2), Some(accounts.alice));
+            // Django accepts the token.
+            assert_eq!(
+                erc721.safe_transfer_from(accounts.alice, accounts.django, 2, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(erc721.owner_of(2), Some(accounts.django));
+            assert_eq!(erc721.balance_of(accounts.alice), 0);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(4, emitted_events.len());
+            assert_transfer_event(
+                &emitted_events[3],
+                Some(accounts.alice),
+                Some(accounts.django),
+                2,
+            );
+        }
+
+        #[ink::test]
+        fn burn_works() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1 for Alice
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Alice owns 1 token.
+            assert_eq!(erc721.balance_of(accounts.alice), 1);
+            // Alice owns token Id 1.
+            assert_eq!(erc721.owner_of(1), Some(accounts.alice));
+            // Destroy token Id 1.
+            assert_eq!(erc721.burn(1), Ok(()));
+            // Alice does not owns tokens.
+            assert_eq!(erc721.balance_of(accounts.alice), 0);
+            // Token Id 1 does not exists
+            assert_eq!(erc721.owner_of(1), None);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(2, emitted_events.len());
+            assert_transfer_event(&emitted_events[1], Some(accounts.alice), None, 1);
+        }
+
+        #[ink::test]
+        fn burn_fails_token_not_found() {
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Try burning a non existent token
+            assert_eq!(erc721.burn(1), Err(Error::TokenNotFound));
+        }
+
+        #[ink::test]
+        fn burn_fails_not_owner() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            // Create a new contract instance.
+            let mut erc721 = Erc721::new();
+            // Create token Id 1 for Alice
+            assert_eq!(erc721.mint(1), Ok(()));
+            // Try burning this token with a different account
+            as_caller(accounts.eve, || {
+                assert_eq!(erc721.burn(1), Err(Error::NotOwner))
+            });
+            // Alice still owns token Id 1.
+            assert_eq!(erc721.owner_of(1), Some(accounts.alice));
+        }
+    }}
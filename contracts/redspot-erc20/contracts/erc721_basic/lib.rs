@@ -0,0 +1,305 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod module;
+
+pub use ::contract::{
+    Env,
+    EnvAccess,
+};
+
+pub use module::{
+    Data,
+    TokenId,
+};
+
+mod erc721_basic {
+    use super::{
+        Data,
+        TokenId,
+    };
+    use ::contract::{
+        Env,
+        EnvAccess,
+        Module,
+        ReceiverAccess,
+        ON_ERC721_RECEIVED,
+    };
+    use ::ink_prelude::vec::Vec;
+
+    /// The ERC-721 error types.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the caller is not the owner of the token.
+        NotOwner,
+        /// Returned if the caller is neither the owner nor an approved account.
+        NotApproved,
+        /// Returned if the token to mint already exists.
+        TokenExists,
+        /// Returned if the token does not exist.
+        TokenNotFound,
+        /// Returned if an approval for the token is already set.
+        CannotInsert,
+        /// Returned if the operation is not allowed, e.g. approve to the zero account.
+        NotAllowed,
+        /// Returned if the receiver contract does not accept the token.
+        TransferRejected,
+    }
+
+    /// The ERC-721 result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    pub trait Erc721EventEmit<E: Env>: EnvAccess<E> {
+        fn emit_event_transfer(
+            &mut self,
+            from: Option<E::AccountId>,
+            to: Option<E::AccountId>,
+            id: TokenId,
+        );
+
+        fn emit_event_approval(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+        );
+
+        fn emit_event_approval_for_all(
+            &mut self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+            approved: bool,
+        );
+    }
+
+    pub trait Erc721Impl<E: Env>: Module<Data<E>> + Erc721EventEmit<E> {
+        // logics
+        fn owner_of_impl(&self, id: TokenId) -> Option<E::AccountId> {
+            self.get_module().get_owner_of(&id)
+        }
+
+        fn balance_of_impl(&self, owner: E::AccountId) -> u32 {
+            self.get_module().get_balance(&owner)
+        }
+
+        fn get_approved_impl(&self, id: TokenId) -> Option<E::AccountId> {
+            self.get_module().get_approved(&id)
+        }
+
+        fn is_approved_for_all_impl(
+            &self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+        ) -> bool {
+            self.get_module().is_approved_for_all(owner, operator)
+        }
+
+        fn mint_impl(&mut self, id: TokenId) -> Result<()> {
+            let caller = Self::caller();
+            self.add_token_to(caller.clone(), id)?;
+            self.emit_event_transfer(None, Some(caller), id);
+            Ok(())
+        }
+
+        fn burn_impl(&mut self, id: TokenId) -> Result<()> {
+            let caller = Self::caller();
+            let owner = self
+                .get_module()
+                .get_owner_of(&id)
+                .ok_or(Error::TokenNotFound)?;
+            if owner != caller {
+                return Err(Error::NotOwner)
+            }
+            self.get_module_mut().token_approval_remove(&id);
+            self.remove_token_from(caller.clone(), id)?;
+            self.emit_event_transfer(Some(caller), None, id);
+            Ok(())
+        }
+
+        fn transfer_impl(&mut self, to: E::AccountId, id: TokenId) -> Result<()> {
+            let caller = Self::caller();
+            self.transfer_token_from(caller, to, id)
+        }
+
+        fn transfer_from_impl(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            self.transfer_token_from(from, to, id)
+        }
+
+        fn approve_impl(&mut self, to: E::AccountId, id: TokenId) -> Result<()> {
+            let caller = Self::caller();
+            let owner = self
+                .get_module()
+                .get_owner_of(&id)
+                .ok_or(Error::TokenNotFound)?;
+            if !(owner == caller
+                || self.get_module().is_approved_for_all(owner, caller.clone()))
+            {
+                return Err(Error::NotAllowed)
+            }
+            if to == E::AccountId::default() {
+                return Err(Error::NotAllowed)
+            }
+            if self.get_module().get_approved(&id).is_some() {
+                return Err(Error::CannotInsert)
+            }
+            self.get_module_mut().token_approval_insert(id, to.clone());
+            // The event names the owner, also when an operator approves.
+            self.emit_event_approval(owner, to, id);
+            Ok(())
+        }
+
+        fn set_approval_for_all_impl(
+            &mut self,
+            to: E::AccountId,
+            approved: bool,
+        ) -> Result<()> {
+            let caller = Self::caller();
+            if to == caller {
+                return Err(Error::NotAllowed)
+            }
+            if approved {
+                self.get_module_mut()
+                    .operator_approval_insert((caller.clone(), to.clone()), approved);
+            } else {
+                self.get_module_mut()
+                    .operator_approval_remove(&(caller.clone(), to.clone()));
+            }
+            self.emit_event_approval_for_all(caller, to, approved);
+            Ok(())
+        }
+
+        /// Transfers like `transfer_from_impl`, a contract recipient must accept the
+        /// token in its `on_erc721_received` callback.
+        fn safe_transfer_from_impl(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+            data: Vec<u8>,
+        ) -> Result<()>
+        where
+            Self: ReceiverAccess<E>,
+        {
+            self.ensure_transfer_allowed(from.clone(), to.clone(), id)?;
+            // The recipient owns the token when its callback runs.
+            self.remove_token_from(from.clone(), id)?;
+            self.add_token_to(to.clone(), id)?;
+            if !Self::check_on_received(
+                &to,
+                ON_ERC721_RECEIVED,
+                (Self::caller(), from.clone(), id, data),
+            ) {
+                // An error does not revert the storage, so the token goes back.
+                self.remove_token_from(to, id)?;
+                self.add_token_to(from, id)?;
+                return Err(Error::TransferRejected)
+            }
+            self.get_module_mut().token_approval_remove(&id);
+            self.emit_event_transfer(Some(from), Some(to), id);
+            Ok(())
+        }
+
+        /// Transfers token `id` `from` the sender to the `to` AccountId.
+        fn transfer_token_from(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            self.ensure_transfer_allowed(from.clone(), to.clone(), id)?;
+            self.move_token(from, to, id)
+        }
+
+        /// Checks that the caller may transfer token `id` `from` its owner to `to`.
+        fn ensure_transfer_allowed(
+            &self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            let caller = Self::caller();
+            if !self.get_module().exists(&id) {
+                return Err(Error::TokenNotFound)
+            }
+            if !self.approved_or_owner(caller, id) {
+                return Err(Error::NotApproved)
+            }
+            if self.get_module().get_owner_of(&id) != Some(from) {
+                return Err(Error::NotOwner)
+            }
+            if to == E::AccountId::default() {
+                return Err(Error::NotAllowed)
+            }
+            Ok(())
+        }
+
+        /// Moves token `id` to `to`, the transfer must be checked before.
+        fn move_token(
+            &mut self,
+            from: E::AccountId,
+            to: E::AccountId,
+            id: TokenId,
+        ) -> Result<()> {
+            self.get_module_mut().token_approval_remove(&id);
+            self.remove_token_from(from.clone(), id)?;
+            self.add_token_to(to.clone(), id)?;
+            self.emit_event_transfer(Some(from), Some(to), id);
+            Ok(())
+        }
+
+        /// Removes token `id` from the owner.
+        fn remove_token_from(&mut self, from: E::AccountId, id: TokenId) -> Result<()> {
+            if self.get_module_mut().token_owner_remove(&id).is_none() {
+                return Err(Error::TokenNotFound)
+            }
+            let count = self.get_module().get_balance(&from);
+            self.get_module_mut()
+                .balance_insert(from, count.saturating_sub(1));
+            Ok(())
+        }
+
+        /// Adds the token `id` to the `to` AccountID.
+        fn add_token_to(&mut self, to: E::AccountId, id: TokenId) -> Result<()> {
+            if self.get_module().exists(&id) {
+                return Err(Error::TokenExists)
+            }
+            if to == E::AccountId::default() {
+                return Err(Error::NotAllowed)
+            }
+            let count = self.get_module().get_balance(&to);
+            self.get_module_mut().balance_insert(to.clone(), count + 1);
+            self.get_module_mut().token_owner_insert(id, to);
+            Ok(())
+        }
+
+        /// Returns true if the AccountId `from` is the owner of token `id`
+        /// or it has been approved on behalf of the token `id` owner.
+        fn approved_or_owner(&self, from: E::AccountId, id: TokenId) -> bool {
+            match self.get_module().get_owner_of(&id) {
+                Some(owner) => {
+                    owner == from
+                        || self.get_module().get_approved(&id) == Some(from.clone())
+                        || self.get_module().is_approved_for_all(owner, from)
+                }
+                None => false,
+            }
+        }
+    }
+
+    impl<E: Env, T: Module<Data<E>> + Erc721EventEmit<E>> Erc721Impl<E>
+        for T
+    {
+    }
+}
+
+pub use erc721_basic::{
+    Erc721EventEmit,
+    Erc721Impl,
+    Error,
+    Result,
+};
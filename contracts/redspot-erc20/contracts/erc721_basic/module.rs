@@ -0,0 +1,121 @@
+pub mod module {
+    pub use ::contract::Env;
+    use ::contract::{
+        Migrate,
+        Namespace,
+    };
+
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::{
+        collections::HashMap as StorageHashMap,
+        traits::{
+            SpreadLayout,
+        },
+    };
+
+    /// The id of a non-fungible token.
+    pub type TokenId = u32;
+
+    #[cfg_attr(feature = "std", derive(::ink_storage::traits::StorageLayout))]
+    #[derive(Debug, SpreadLayout)]
+    pub struct Data<E: Env> {
+        /// Mapping from token to owner.
+        token_owner: StorageHashMap<TokenId, E::AccountId>,
+        /// Mapping from token to approvals users.
+        token_approvals: StorageHashMap<TokenId, E::AccountId>,
+        /// Mapping from owner to number of owned token.
+        owned_tokens_count: StorageHashMap<E::AccountId, u32>,
+        /// Mapping from owner to operator approvals.
+        operator_approvals: StorageHashMap<(E::AccountId, E::AccountId), bool>,
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn new() -> Self {
+            Self {
+                token_owner: StorageHashMap::new(),
+                token_approvals: StorageHashMap::new(),
+                owned_tokens_count: StorageHashMap::new(),
+                operator_approvals: StorageHashMap::new(),
+            }
+        }
+    }
+
+    impl<E: Env> Namespace for Data<E> {
+        const NAMESPACE: &'static [u8] = b"erc721_basic::Data";
+    }
+
+    impl<E: Env> Migrate for Data<E> {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, _from: u32) {}
+    }
+
+    impl<E: Env> Data<E> {
+        pub fn get_owner_of(&self, id: &TokenId) -> Option<E::AccountId> {
+            self.token_owner.get(id).cloned()
+        }
+
+        pub fn get_approved(&self, id: &TokenId) -> Option<E::AccountId> {
+            self.token_approvals.get(id).cloned()
+        }
+
+        pub fn get_balance(&self, owner: &E::AccountId) -> u32 {
+            self.owned_tokens_count.get(owner).copied().unwrap_or(0)
+        }
+
+        pub fn is_approved_for_all(
+            &self,
+            owner: E::AccountId,
+            operator: E::AccountId,
+        ) -> bool {
+            self.operator_approvals
+                .get(&(owner, operator))
+                .copied()
+                .unwrap_or(false)
+        }
+
+        pub fn exists(&self, id: &TokenId) -> bool {
+            self.token_owner.contains_key(id)
+        }
+
+        pub fn token_owner_insert(&mut self, id: TokenId, owner: E::AccountId) {
+            self.token_owner.insert(id, owner);
+        }
+
+        pub fn token_owner_remove(&mut self, id: &TokenId) -> Option<E::AccountId> {
+            self.token_owner.take(id)
+        }
+
+        pub fn token_approval_insert(&mut self, id: TokenId, to: E::AccountId) {
+            self.token_approvals.insert(id, to);
+        }
+
+        pub fn token_approval_remove(&mut self, id: &TokenId) -> Option<E::AccountId> {
+            self.token_approvals.take(id)
+        }
+
+        pub fn balance_insert(&mut self, owner: E::AccountId, count: u32) {
+            self.owned_tokens_count.insert(owner, count);
+        }
+
+        pub fn operator_approval_insert(
+            &mut self,
+            owner_operator: (E::AccountId, E::AccountId),
+            approved: bool,
+        ) {
+            self.operator_approvals.insert(owner_operator, approved);
+        }
+
+        pub fn operator_approval_remove(
+            &mut self,
+            owner_operator: &(E::AccountId, E::AccountId),
+        ) {
+            self.operator_approvals.take(owner_operator);
+        }
+    }
+}
+
+pub use module::{
+    Data,
+    TokenId,
+};
@@ -0,0 +1,123 @@
+#![no_main]
+
+use ::contract::{
+    MockEnv,
+    Module,
+};
+use ::contract_test::{
+    accounts,
+    Balance,
+    ACCOUNTS,
+};
+use ::erc20_basic::{
+    Data,
+    Erc20Impl,
+    Error,
+    MockErc20,
+};
+use libfuzzer_sys::{
+    arbitrary::{
+        self,
+        Arbitrary,
+    },
+    fuzz_target,
+};
+
+/// An operation of `Erc20Impl`, the accounts are indices of the default accounts
+/// modulo their number.
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Transfer {
+        caller: u8,
+        to: u8,
+        value: Balance,
+    },
+    Approve {
+        caller: u8,
+        spender: u8,
+        value: Balance,
+    },
+    TransferFrom {
+        caller: u8,
+        from: u8,
+        to: u8,
+        value: Balance,
+    },
+    Mint {
+        to: u8,
+        value: Balance,
+    },
+    Burn {
+        from: u8,
+        value: Balance,
+    },
+}
+
+fn account(index: u8) -> ::contract_test::AccountId {
+    accounts()[index as usize % ACCOUNTS]
+}
+
+fuzz_target!(|input: (Balance, Vec<Op>)| {
+    let (initial_supply, ops) = input;
+    MockEnv::run(|| {
+        let mut token = MockErc20::new(Data::new());
+        token.new_impl(initial_supply);
+
+        let mut succeeded = 1;
+        for op in &ops {
+            let result = match *op {
+                Op::Transfer { caller, to, value } => {
+                    MockEnv::set_caller(account(caller));
+                    token.transfer_impl(account(to), value)
+                }
+                Op::Approve {
+                    caller,
+                    spender,
+                    value,
+                } => {
+                    MockEnv::set_caller(account(caller));
+                    token.approve_impl(account(spender), value)
+                }
+                Op::TransferFrom {
+                    caller,
+                    from,
+                    to,
+                    value,
+                } => {
+                    MockEnv::set_caller(account(caller));
+                    token.transfer_from_impl(account(from), account(to), value)
+                }
+                Op::Mint { to, value } => {
+                    let total_supply = token.get_module().get_total_supply();
+                    let result = token.mint_impl(account(to), value);
+                    if total_supply.checked_add(value).is_none() {
+                        assert_eq!(result, Err(Error::Overflow), "mint of {}", value);
+                    }
+                    result
+                }
+                Op::Burn { from, value } => token.burn_impl(account(from), value),
+            };
+            if result.is_ok() {
+                succeeded += 1;
+            }
+
+            let balances = accounts()
+                .iter()
+                .map(|account| token.get_module().get_balance(*account))
+                .sum::<Balance>();
+            assert_eq!(
+                balances,
+                token.get_module().get_total_supply(),
+                "balances do not add up to the total supply after {:?}",
+                op
+            );
+            // Every successful operation emits one event, a failed one none.
+            assert_eq!(
+                MockEnv::events().len(),
+                succeeded,
+                "invalid events after {:?}",
+                op
+            );
+        }
+    })
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use contracts_fuzz::{
+    fuzz_erc20_calls,
+    Call,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|calls: Vec<Call>| {
+    fuzz_erc20_calls::<contracts_fuzz::FuzzErc20>(&calls);
+});
@@ -0,0 +1,58 @@
+#![no_main]
+
+use ::contract_test::{
+    accounts,
+    as_caller,
+};
+use ::owner::selectors;
+use ::owner_contract::owner::Owner;
+use contracts_fuzz::{
+    dispatch_decodes,
+    run,
+    Call,
+};
+use libfuzzer_sys::fuzz_target;
+use scale::Decode;
+
+fuzz_target!(|calls: Vec<Call>| {
+    run(|| {
+        // The constructor is called by Alice.
+        let mut contract = Owner::new();
+        let mut owner = Some(accounts()[0]);
+        for call in calls {
+            let dispatched = dispatch_decodes::<Owner>(&call.data);
+            let input = &mut &call.data[..];
+            let selector = match <[u8; 4]>::decode(input) {
+                Ok(selector) => selector,
+                Err(_) => continue,
+            };
+            let caller = call.caller();
+            match selector {
+                selectors::RENOUNCE_OWNERSHIP => {
+                    assert!(dispatched, "the dispatch does not decode {:?}", call);
+                    // Called by another account than the owner, it panics.
+                    if owner == Some(caller) {
+                        as_caller(caller, || contract.renounce_ownership());
+                        owner = None;
+                    }
+                }
+                selectors::TRANSFER_OWNERSHIP => {
+                    let new_owner = match Decode::decode(input) {
+                        Ok(new_owner) => new_owner,
+                        Err(_) => continue,
+                    };
+                    assert!(dispatched, "the dispatch does not decode {:?}", call);
+                    if owner == Some(caller) {
+                        as_caller(caller, || contract.transfer_ownership(new_owner));
+                        owner = Some(new_owner);
+                    }
+                }
+                selectors::GET_OWNER => {
+                    assert!(dispatched, "the dispatch does not decode {:?}", call);
+                }
+                _ => continue,
+            }
+            assert_eq!(contract.get_owner(), owner, "invalid owner after {:?}", call);
+        }
+    })
+});
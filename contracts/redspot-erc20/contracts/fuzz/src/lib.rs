@@ -0,0 +1,251 @@
+//! Fuzz targets of the token contracts, run with `cargo fuzz run <target>` from the
+//! directory of this crate.
+//!
+//! The call data targets decode the input with the message dispatch generated by
+//! ink!, and check that it reads the same message as a decoder written from the
+//! published selectors. The execution of the generated dispatch is not fuzzed:
+//! ink!'s off-chain `return_value` exits the process, so the messages are executed
+//! through the message methods instead.
+//!
+//! The documented panics, e.g. an owner-only message called by another account,
+//! are avoided by the targets instead of being caught.
+
+use ::contract_test::{
+    accounts,
+    as_caller,
+    erc20_contract,
+    AccountId,
+    Balance,
+    Erc20Contract,
+    ACCOUNTS,
+    ERC20_MESSAGES,
+    INITIAL_SUPPLY,
+};
+use ::erc20::erc20::Erc20;
+use ::erc20_basic::selectors;
+use ::erc20owner::erc20owner::{
+    Erc20Ownable,
+    Error as Erc20OwnableError,
+};
+use ::ink_env::DefaultEnvironment;
+use ::ink_lang::MessageDispatcher;
+use core::ops::Deref;
+use libfuzzer_sys::arbitrary::{
+    self,
+    Arbitrary,
+};
+use scale::{
+    Decode,
+    Encode,
+    Input,
+    Output,
+};
+
+erc20_contract!(FuzzErc20(Erc20), ::erc20_basic::Error);
+erc20_contract!(FuzzErc20Ownable(Erc20Ownable), Erc20OwnableError);
+
+/// A call to a contract, `data` is the call data, selector first.
+#[derive(Arbitrary, Debug)]
+pub struct Call {
+    /// The index of the caller in the default accounts, modulo their number.
+    pub caller: u8,
+    pub data: Vec<u8>,
+}
+
+impl Call {
+    /// Returns the caller of the call.
+    pub fn caller(&self) -> AccountId {
+        accounts()[self.caller as usize % ACCOUNTS]
+    }
+}
+
+/// Runs `f` in a fresh off-chain environment.
+pub fn run(f: impl FnOnce()) {
+    ::ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        f();
+        Ok(())
+    })
+    .expect("Cannot run the off-chain environment")
+}
+
+/// Returns the number of bytes of `data` read by the dispatch of the contract `T`,
+/// or `None` if it does not decode `data`.
+pub fn dispatch_decode<T>(data: &[u8]) -> Option<usize>
+where
+    T: MessageDispatcher,
+    <T as MessageDispatcher>::Type: Decode,
+{
+    let input = &mut &data[..];
+    <<T as MessageDispatcher>::Type as Decode>::decode(input).ok()?;
+    Some(data.len() - input.len())
+}
+
+/// Returns whether the dispatch of the contract `T` decodes `data`.
+pub fn dispatch_decodes<T>(data: &[u8]) -> bool
+where
+    T: MessageDispatcher,
+    <T as MessageDispatcher>::Type: Decode,
+{
+    dispatch_decode::<T>(data).is_some()
+}
+
+/// A standard ERC-20 message, decoded from the selectors of `erc20_basic`.
+#[derive(Debug)]
+pub enum Erc20Message {
+    TotalSupply,
+    BalanceOf(AccountId),
+    Allowance(AccountId, AccountId),
+    Transfer(AccountId, Balance),
+    Approve(AccountId, Balance),
+    TransferFrom(AccountId, AccountId, Balance),
+}
+
+impl Decode for Erc20Message {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let message = match <[u8; 4]>::decode(input)? {
+            selectors::TOTAL_SUPPLY => Self::TotalSupply,
+            selectors::BALANCE_OF => Self::BalanceOf(Decode::decode(input)?),
+            selectors::ALLOWANCE => {
+                Self::Allowance(Decode::decode(input)?, Decode::decode(input)?)
+            }
+            selectors::TRANSFER => {
+                Self::Transfer(Decode::decode(input)?, Decode::decode(input)?)
+            }
+            selectors::APPROVE => {
+                Self::Approve(Decode::decode(input)?, Decode::decode(input)?)
+            }
+            selectors::TRANSFER_FROM => {
+                Self::TransferFrom(
+                    Decode::decode(input)?,
+                    Decode::decode(input)?,
+                    Decode::decode(input)?,
+                )
+            }
+            _ => return Err("Unknown ERC-20 selector".into()),
+        };
+        Ok(message)
+    }
+}
+
+impl Encode for Erc20Message {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        match *self {
+            Self::TotalSupply => selectors::TOTAL_SUPPLY.encode_to(dest),
+            Self::BalanceOf(owner) => (selectors::BALANCE_OF, owner).encode_to(dest),
+            Self::Allowance(owner, spender) => {
+                (selectors::ALLOWANCE, owner, spender).encode_to(dest)
+            }
+            Self::Transfer(to, value) => (selectors::TRANSFER, to, value).encode_to(dest),
+            Self::Approve(spender, value) => {
+                (selectors::APPROVE, spender, value).encode_to(dest)
+            }
+            Self::TransferFrom(from, to, value) => {
+                (selectors::TRANSFER_FROM, from, to, value).encode_to(dest)
+            }
+        }
+    }
+}
+
+impl Erc20Message {
+    /// Returns whether `data` starts with the selector of a standard message.
+    pub fn has_selector(data: &[u8]) -> bool {
+        ERC20_MESSAGES
+            .iter()
+            .any(|(_, selector)| data.starts_with(selector))
+    }
+
+    /// Returns the accounts passed to the message.
+    pub fn accounts(&self) -> Vec<AccountId> {
+        match *self {
+            Self::TotalSupply => vec![],
+            Self::BalanceOf(owner) => vec![owner],
+            Self::Allowance(owner, spender) => vec![owner, spender],
+            Self::Transfer(to, _) => vec![to],
+            Self::Approve(spender, _) => vec![spender],
+            Self::TransferFrom(from, to, _) => vec![from, to],
+        }
+    }
+
+    /// Calls the message on `token`.
+    pub fn call<T: Erc20Contract>(&self, token: &mut T) {
+        match *self {
+            Self::TotalSupply => {
+                token.total_supply();
+            }
+            Self::BalanceOf(owner) => {
+                token.balance_of(owner);
+            }
+            Self::Allowance(owner, spender) => {
+                token.allowance(owner, spender);
+            }
+            Self::Transfer(to, value) => {
+                let _ = token.transfer(to, value);
+            }
+            Self::Approve(spender, value) => {
+                let _ = token.approve(spender, value);
+            }
+            Self::TransferFrom(from, to, value) => {
+                let _ = token.transfer_from(from, to, value);
+            }
+        }
+    }
+}
+
+/// Runs `calls` on a new token `T`, a wrapper of `erc20_contract!`, checks that the
+/// dispatch of the contract reads every standard message as `Erc20Message` does and
+/// that the balances of all the accounts seen add up to the total supply.
+pub fn fuzz_erc20_calls<T>(calls: &[Call])
+where
+    T: Erc20Contract + Deref,
+    T::Target: MessageDispatcher,
+    <T::Target as MessageDispatcher>::Type: Decode,
+{
+    run(|| {
+        let mut token = T::new(INITIAL_SUPPLY);
+        let mut seen = accounts().to_vec();
+        for call in calls {
+            let dispatched = dispatch_decode::<T::Target>(&call.data);
+            let message = match Erc20Message::decode(&mut &call.data[..]) {
+                Ok(message) => message,
+                Err(_) => {
+                    if Erc20Message::has_selector(&call.data) {
+                        assert_eq!(dispatched, None, "the dispatch decodes {:?}", call);
+                    }
+                    continue
+                }
+            };
+            // The dispatch reads the same bytes, which encode the same message.
+            let encoded = message.encode();
+            assert_eq!(
+                dispatched,
+                Some(encoded.len()),
+                "the dispatch does not decode {:?}",
+                message
+            );
+            assert_eq!(
+                &call.data[..encoded.len()],
+                &encoded[..],
+                "the dispatch decodes another message than {:?}",
+                message
+            );
+
+            for account in message.accounts() {
+                if !seen.contains(&account) {
+                    seen.push(account);
+                }
+            }
+            as_caller(call.caller(), || message.call(&mut token));
+
+            let balances = seen
+                .iter()
+                .map(|account| token.balance_of(*account))
+                .sum::<Balance>();
+            assert_eq!(
+                balances,
+                token.total_supply(),
+                "balances do not add up to the total supply after {:?}",
+                message
+            );
+        }
+    })
+}
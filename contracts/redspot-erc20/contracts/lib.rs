@@ -23,18 +23,34 @@ pub mod erc20 {
         Env,
         StaticEnv,
     };
+    use ::ink_prelude::{
+        string::String,
+        vec::Vec,
+    };
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::lazy::Lazy;
 
     use ::erc20_basic::{
         Erc20EventEmit,
         Erc20Impl,
+        HoldersImpl,
         Result,
         Data as Erc20Data,
+        HoldersData,
     };
 
     /// A simple ERC-20 contract.
     #[ink(storage)]
     pub struct Erc20 {
-        data_erc20: Erc20Data<Erc20>
+        data_erc20: ::contract::Namespaced<Erc20Data<Erc20>>,
+        /// Enumerable index of the token holders.
+        data_holders: ::contract::Namespaced<HoldersData<Erc20>>,
+        /// Name of the token.
+        name: Lazy<Option<String>>,
+        /// Symbol of the token.
+        symbol: Lazy<Option<String>>,
+        /// Number of decimals of the token amounts.
+        decimals: Lazy<u8>,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -88,6 +104,30 @@ pub mod erc20 {
             &mut self.data_erc20
         }
     }
+
+    impl ::contract::Module<HoldersData<Erc20>> for Erc20 {
+        fn get_module(&self) -> &HoldersData<Erc20> {
+            &self.data_holders
+        }
+
+        fn get_module_mut(&mut self) -> &mut HoldersData<Erc20> {
+            &mut self.data_holders
+        }
+    }
+
+    impl ::contract::ReceiverAccess<Erc20> for Erc20 {
+        fn call_receiver<Args: scale::Encode>(
+            to: &AccountId,
+            selector: [u8; 4],
+            args: Args,
+        ) -> Result<[u8; 4], ::ink_env::Error> {
+            ::contract::call_receiver::<::ink_env::DefaultEnvironment, Args>(
+                to,
+                selector,
+                args,
+            )
+        }
+    }
     // TODO: Make by macro
 
     // TODO: Event in ink!
@@ -113,41 +153,97 @@ pub mod erc20 {
                 value,
             });
         }
+
+        fn after_token_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            _value: Balance,
+        ) {
+            self.update_holders_impl(from, to);
+        }
     }
     // TODO: Event in ink!
 
     // impl
     impl Erc20 {
         /// Creates a new ERC-20 contract with the specified initial supply.
-        #[ink(constructor)]
+        #[ink(constructor, selector = "0x9BAE9D5E")]
         pub fn new(initial_supply: Balance) -> Self {
             let mut instance = Self {
-                data_erc20: Erc20Data::new()
+                data_erc20: ::contract::Namespaced::init(Erc20Data::new()),
+                data_holders: ::contract::Namespaced::init(HoldersData::new()),
+                name: Lazy::new(None),
+                symbol: Lazy::new(None),
+                decimals: Lazy::new(0),
             };
             instance.new_impl(initial_supply);
             instance
         }
 
+        /// Creates a new ERC-20 contract with the token metadata, the initial supply
+        /// is given to `owner`.
+        ///
+        /// Used by factories, which are the caller of the constructor.
+        #[ink(constructor, selector = "0xC65BC726")]
+        pub fn new_with_metadata(
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+            initial_supply: Balance,
+            owner: AccountId,
+        ) -> Self {
+            let mut instance = Self {
+                data_erc20: ::contract::Namespaced::init(Erc20Data::new()),
+                data_holders: ::contract::Namespaced::init(HoldersData::new()),
+                name: Lazy::new(name),
+                symbol: Lazy::new(symbol),
+                decimals: Lazy::new(decimals),
+            };
+            instance
+                .mint_impl(owner, initial_supply)
+                .expect("minting the initial supply cannot fail");
+            instance
+        }
+
+        /// Returns the name of the token.
+        #[ink(message, selector = "0x53DB29AE")]
+        pub fn token_name(&self) -> Option<String> {
+            (*self.name).clone()
+        }
+
+        /// Returns the symbol of the token.
+        #[ink(message, selector = "0xD733EA17")]
+        pub fn token_symbol(&self) -> Option<String> {
+            (*self.symbol).clone()
+        }
+
+        /// Returns the number of decimals of the token amounts.
+        #[ink(message, selector = "0x9715E3CA")]
+        pub fn token_decimals(&self) -> u8 {
+            *self.decimals
+        }
+
         /// Returns the total token supply.
-        #[ink(message)]
+        #[ink(message, selector = "0xDB6375A8")]
         pub fn total_supply(&self) -> Balance {
-            self.get_module().get_total_supply()
+            Module::<Erc20Data<Erc20>>::get_module(self).get_total_supply()
         }
 
         /// Returns the account balance for the specified `owner`.
         ///
         /// Returns `0` if the account is non-existent.
-        #[ink(message)]
+        #[ink(message, selector = "0x0F755A56")]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
-            self.get_module().get_balance(owner)
+            Module::<Erc20Data<Erc20>>::get_module(self).get_balance(owner)
         }
 
         /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
         ///
         /// Returns `0` if no allowance has been set `0`.
-        #[ink(message)]
+        #[ink(message, selector = "0x6A00165E")]
         pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
-            self.get_module().get_allowance(owner, spender)
+            Module::<Erc20Data<Erc20>>::get_module(self).get_allowance(owner, spender)
         }
 
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
@@ -158,7 +254,7 @@ pub mod erc20 {
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
-        #[ink(message)]
+        #[ink(message, selector = "0x84A15DA1")]
         pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
             self.transfer_impl(to, value)
         }
@@ -169,7 +265,7 @@ pub mod erc20 {
         /// If this function is called again it overwrites the current allowance with `value`.
         ///
         /// An `Approval` event is emitted.
-        #[ink(message)]
+        #[ink(message, selector = "0x681266A0")]
         pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
             self.approve_impl(spender, value)
         }
@@ -188,7 +284,7 @@ pub mod erc20 {
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the the account balance of `from`.
-        #[ink(message)]
+        #[ink(message, selector = "0x0B396F18")]
         pub fn transfer_from(
             &mut self,
             from: AccountId,
@@ -197,6 +293,64 @@ pub mod erc20 {
         ) -> Result<()> {
             self.transfer_from_impl(from, to, value)
         }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`,
+        /// a contract `to` must accept them in its `on_erc20_received` callback.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        ///
+        /// Returns `TransferRejected` error if the receiver contract does not accept
+        /// the tokens.
+        #[ink(message)]
+        pub fn safe_transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.safe_transfer_impl(to, value, data)
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`,
+        /// a contract `to` must accept them in its `on_erc20_received` callback.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns the errors of `transfer_from` and `TransferRejected` if the
+        /// receiver contract does not accept the tokens.
+        #[ink(message)]
+        pub fn safe_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.safe_transfer_from_impl(from, to, value, data)
+        }
+
+        /// Returns the number of accounts with a non-zero balance.
+        #[ink(message)]
+        pub fn holders_count(&self) -> u32 {
+            self.holders_count_impl()
+        }
+
+        /// Returns at most `limit` accounts with a non-zero balance, from the index
+        /// `start`.
+        ///
+        /// The order changes when an account's balance goes to zero, the last holder
+        /// then takes its index. A page has at most `MAX_HOLDERS_PAGE` holders.
+        #[ink(message)]
+        pub fn holders(&self, start: u32, limit: u32) -> Vec<AccountId> {
+            self.holders_impl(start, limit)
+        }
     }
 
     /// Unit tests.
@@ -204,81 +358,48 @@ pub mod erc20 {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
-        use ink_env::{
-            hash::{
-                Blake2x256,
-                CryptoHash,
-                HashOutput,
-            },
-            Clear,
-        };
 
-        use ::erc20_basic::Error;
+        use ::contract_test::{
+            as_caller,
+            assert_event,
+            default_accounts,
+        };
+        use ::erc20_basic::{
+            Error,
+            MAX_HOLDERS_PAGE,
+        };
 
         type Event = <Erc20 as ::ink_lang::BaseEvent>::Type;
 
         use ink_lang as ink;
 
+        ::contract_test::event_topics!(Transfer, "Erc20::Transfer", [from, to]);
+        ::contract_test::event_topics!(Approval, "Erc20::Approval", [owner, spender]);
+        ::contract_test::erc20_contract!(Erc20, Error);
+
         fn assert_transfer_event(
             event: &ink_env::test::EmittedEvent,
-            expected_from: Option<AccountId>,
-            expected_to: Option<AccountId>,
-            expected_value: Balance,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
         ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-                .expect("encountered invalid contract event data buffer");
-            if let Event::Transfer(Transfer { from, to, value }) = decoded_event {
-                assert_eq!(from, expected_from, "encountered invalid Transfer.from");
-                assert_eq!(to, expected_to, "encountered invalid Transfer.to");
-                assert_eq!(value, expected_value, "encountered invalid Trasfer.value");
-            } else {
-                panic!("encountered unexpected event kind: expected a Transfer event")
-            }
-            fn encoded_into_hash<T>(entity: &T) -> Hash
-            where
-                T: scale::Encode,
-            {
-                let mut result = Hash::clear();
-                let len_result = result.as_ref().len();
-                let encoded = entity.encode();
-                let len_encoded = encoded.len();
-                if len_encoded <= len_result {
-                    result.as_mut()[..len_encoded].copy_from_slice(&encoded);
-                    return result
-                }
-                let mut hash_output =
-                    <<Blake2x256 as HashOutput>::Type as Default>::default();
-                <Blake2x256 as CryptoHash>::hash(&encoded, &mut hash_output);
-                let copy_len = core::cmp::min(hash_output.len(), len_result);
-                result.as_mut()[0..copy_len].copy_from_slice(&hash_output[0..copy_len]);
-                result
-            }
-            let expected_topics = vec![
-                encoded_into_hash(&PrefixedValue {
-                    value: b"Erc20::Transfer",
-                    prefix: b"",
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20::Transfer::from",
-                    value: &expected_from,
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20::Transfer::to",
-                    value: &expected_to,
-                }),
-                encoded_into_hash(&PrefixedValue {
-                    prefix: b"Erc20::Transfer::value",
-                    value: &expected_value,
-                }),
-            ];
-            for (n, (actual_topic, expected_topic)) in
-                event.topics.iter().zip(expected_topics).enumerate()
-            {
-                let topic = actual_topic
-                    .decode::<Hash>()
-                    .expect("encountered invalid topic encoding");
-                assert_eq!(topic, expected_topic, "encountered invalid topic at {}", n);
-            }
+            assert_event::<Event, _>(event, Transfer { from, to, value });
+        }
+
+        fn assert_approval_event(
+            event: &ink_env::test::EmittedEvent,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) {
+            assert_event::<Event, _>(
+                event,
+                Approval {
+                    owner,
+                    spender,
+                    value,
+                },
+            );
         }
 
         /// The default constructor does its job.
@@ -299,6 +420,49 @@ pub mod erc20 {
             );
         }
 
+        /// The module data is stored at the root key of its namespace.
+        #[ink::test]
+        fn module_data_is_namespaced() {
+            use ::contract::Namespace;
+            use ::ink_primitives::{
+                Key,
+                KeyPtr,
+            };
+            use ::ink_storage::traits::SpreadLayout;
+
+            let erc20 = Erc20::new(100);
+            SpreadLayout::push_spread(&erc20, &mut KeyPtr::from(Key::from([0x00; 32])));
+
+            // The total supply is the first field of the module data.
+            let root_key = <Erc20Data<Erc20> as Namespace>::root_key();
+            assert_eq!(
+                ink_env::get_contract_storage::<Balance>(&root_key),
+                Ok(Some(100))
+            );
+        }
+
+        /// The constructor with metadata gives the initial supply to the owner.
+        #[ink::test]
+        fn new_with_metadata_works() {
+            let accounts = default_accounts::<Erc20>();
+            let erc20 = Erc20::new_with_metadata(
+                Some(String::from("Token")),
+                Some(String::from("TKN")),
+                18,
+                100,
+                accounts.bob,
+            );
+            assert_eq!(erc20.token_name(), Some(String::from("Token")));
+            assert_eq!(erc20.token_symbol(), Some(String::from("TKN")));
+            assert_eq!(erc20.token_decimals(), 18);
+            assert_eq!(erc20.total_supply(), 100);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.alice), 0);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_transfer_event(&emitted_events[0], None, Some(accounts.bob), 100);
+        }
+
         /// The total supply was applied.
         #[ink::test]
         fn total_supply_works() {
@@ -329,9 +493,7 @@ pub mod erc20 {
                 Some(AccountId::from([0x01; 32])),
                 100,
             );
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<Erc20>();
             // Alice owns all the tokens on deployment
             assert_eq!(erc20.balance_of(accounts.alice), 100);
             // Bob does not owns tokens
@@ -343,9 +505,7 @@ pub mod erc20 {
             // Constructor works.
             let mut erc20 = Erc20::new(100);
             // Transfer event triggered during initial construction.
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<Erc20>();
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
             // Alice transfers 10 tokens to Bob.
@@ -371,34 +531,58 @@ pub mod erc20 {
             );
         }
 
+        #[ink::test]
+        fn holders_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = default_accounts::<Erc20>();
+            assert_eq!(erc20.holders_count(), 1);
+            assert_eq!(erc20.holders(0, 10), vec![accounts.alice]);
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            // A second transfer does not add Bob again.
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.holders_count(), 3);
+            assert_eq!(
+                erc20.holders(0, 10),
+                vec![accounts.alice, accounts.bob, accounts.charlie]
+            );
+            assert_eq!(erc20.holders(1, 1), vec![accounts.bob]);
+
+            // Alice's balance goes to zero, Charlie takes her index.
+            assert_eq!(erc20.transfer(accounts.bob, 60), Ok(()));
+            assert_eq!(erc20.holders_count(), 2);
+            assert_eq!(erc20.holders(0, 10), vec![accounts.charlie, accounts.bob]);
+            assert_eq!(erc20.holders(2, 10), Vec::new());
+            assert_eq!(erc20.holders(1, u32::MAX), vec![accounts.bob]);
+        }
+
+        #[ink::test]
+        fn holders_page_is_capped() {
+            let mut erc20 = Erc20::new(1000);
+            for n in 0..MAX_HOLDERS_PAGE as u8 {
+                let holder = AccountId::from([0x10 + n; 32]);
+                assert_eq!(erc20.transfer(holder, 1), Ok(()));
+            }
+            assert_eq!(erc20.holders_count(), MAX_HOLDERS_PAGE + 1);
+            assert_eq!(
+                erc20.holders(0, u32::MAX).len(),
+                MAX_HOLDERS_PAGE as usize
+            );
+            assert_eq!(erc20.holders(MAX_HOLDERS_PAGE, u32::MAX).len(), 1);
+        }
+
         #[ink::test]
         fn invalid_transfer_should_fail() {
             // Constructor works.
             let mut erc20 = Erc20::new(100);
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<Erc20>();
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
 
             // Bob fails to transfers 10 tokens to Eve.
             assert_eq!(
-                erc20.transfer(accounts.eve, 10),
+                as_caller(accounts.bob, || erc20.transfer(accounts.eve, 10)),
                 Err(Error::InsufficientBalance)
             );
             // Alice owns all the tokens.
@@ -422,9 +606,7 @@ pub mod erc20 {
             // Constructor works.
             let mut erc20 = Erc20::new(100);
             // Transfer event triggered during initial construction.
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<Erc20>();
 
             // Bob fails to transfer tokens owned by Alice.
             assert_eq!(
@@ -437,25 +619,11 @@ pub mod erc20 {
             // The approve event takes place.
             assert_eq!(ink_env::test::recorded_events().count(), 2);
 
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call.
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller.
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
-
             // Bob transfers tokens from Alice to Eve.
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, 10),
+                as_caller(accounts.bob, || {
+                    erc20.transfer_from(accounts.alice, accounts.eve, 10)
+                }),
                 Ok(())
             );
             // Eve owns tokens.
@@ -470,7 +638,12 @@ pub mod erc20 {
                 Some(AccountId::from([0x01; 32])),
                 100,
             );
-            // The second event `emitted_events[1]` is an Approve event that we skip checking.
+            assert_approval_event(
+                &emitted_events[1],
+                AccountId::from([0x01; 32]),
+                AccountId::from([0x02; 32]),
+                10,
+            );
             assert_transfer_event(
                 &emitted_events[2],
                 Some(AccountId::from([0x01; 32])),
@@ -482,36 +655,20 @@ pub mod erc20 {
         #[ink::test]
         fn allowance_must_not_change_on_failed_transfer() {
             let mut erc20 = Erc20::new(100);
-            let accounts =
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                    .expect("Cannot get accounts");
+            let accounts = default_accounts::<Erc20>();
 
             // Alice approves Bob for token transfers on her behalf.
             let alice_balance = erc20.balance_of(accounts.alice);
             let initial_allowance = alice_balance + 2;
             assert_eq!(erc20.approve(accounts.bob, initial_allowance), Ok(()));
 
-            // Get contract address.
-            let callee = ink_env::account_id::<ink_env::DefaultEnvironment>()
-                .unwrap_or([0x0; 32].into());
-            // Create call.
-            let mut data =
-                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller.
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
-
             // Bob tries to transfer tokens from Alice to Eve.
             let emitted_events_before =
                 ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1),
+                as_caller(accounts.bob, || {
+                    erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1)
+                }),
                 Err(Error::InsufficientBalance)
             );
             // Allowance must have stayed the same
@@ -524,27 +681,57 @@ pub mod erc20 {
                 ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(emitted_events_before.len(), emitted_events_after.len());
         }
-    }
 
-    /// For calculating the event topic hash.
-    struct PrefixedValue<'a, 'b, T> {
-        pub prefix: &'a [u8],
-        pub value: &'b T,
-    }
+        // Needs the stubs of the cross-contract calls.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn safe_transfer_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = default_accounts::<Erc20>();
+            // Django is a receiver contract accepting tokens, Eve one rejecting them.
+            ::contract::set_receiver(
+                &accounts.django,
+                Some(::contract::ON_ERC20_RECEIVED),
+            );
+            ::contract::set_receiver(&accounts.eve, Some([0x00; 4]));
+
+            // Plain accounts can always receive tokens.
+            assert_eq!(erc20.safe_transfer(accounts.bob, 10, Vec::new()), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            // Django accepts the tokens.
+            assert_eq!(erc20.safe_transfer(accounts.django, 10, Vec::new()), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.django), 10);
+            // Eve rejects the tokens.
+            assert_eq!(
+                erc20.safe_transfer(accounts.eve, 10, Vec::new()),
+                Err(Error::TransferRejected)
+            );
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 80);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_transfer_event(
+                &emitted_events[2],
+                Some(AccountId::from([0x01; 32])),
+                Some(AccountId::from([0x04; 32])),
+                10,
+            );
+        }
 
-    impl<X> scale::Encode for PrefixedValue<'_, '_, X>
-    where
-        X: scale::Encode,
-    {
-        #[inline]
-        fn size_hint(&self) -> usize {
-            self.prefix.size_hint() + self.value.size_hint()
+        proptest::proptest! {
+            #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+            /// Random calls keep the balances, allowances and events consistent.
+            #[test]
+            fn erc20_invariants_hold(ops in ::contract_test::erc20_ops(32)) {
+                ::contract_test::check_erc20_ops::<Erc20>(&ops);
+            }
         }
 
-        #[inline]
-        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
-            self.prefix.encode_to(dest);
-            self.value.encode_to(dest);
+        #[test]
+        fn message_selectors_are_stable() {
+            ::contract_test::assert_message_selectors!(::contract_test::ERC20_MESSAGES);
         }
     }
 }
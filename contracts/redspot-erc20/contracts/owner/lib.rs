@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "test-utils")]
+mod mock;
 mod module;
 
 pub use ::contract::{
@@ -7,8 +9,21 @@ pub use ::contract::{
     EnvAccess,
 };
 
+#[cfg(feature = "test-utils")]
+pub use mock::MockOwner;
 pub use module::Data;
 
+/// Selectors of the owner messages, contracts fix their messages to these so that
+/// ownership can be managed the same way on all of them.
+pub mod selectors {
+    /// Selector of `renounce_ownership()`.
+    pub const RENOUNCE_OWNERSHIP: [u8; 4] = [0x8c, 0x90, 0x06, 0x5b];
+    /// Selector of `transfer_ownership(new_owner)`.
+    pub const TRANSFER_OWNERSHIP: [u8; 4] = [0x10, 0x7e, 0x33, 0xea];
+    /// Selector of `get_owner()`.
+    pub const GET_OWNER: [u8; 4] = [0x07, 0xfc, 0xd0, 0xb1];
+}
+
 mod owner {
     use super::Data;
     use ::contract::{
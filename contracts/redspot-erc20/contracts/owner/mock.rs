@@ -0,0 +1,67 @@
+pub mod mock {
+    use crate::{
+        Data,
+        EventEmit,
+    };
+    use ::contract::{
+        MockContract,
+        MockEnv,
+    };
+
+    /// A contract made of the `owner` module alone.
+    pub type MockOwner = MockContract<Data<MockEnv>>;
+
+    impl EventEmit<MockEnv> for MockOwner {}
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::Impl;
+        use ::contract::{
+            Env,
+            EnvAccess,
+        };
+
+        type AccountId = <MockEnv as Env>::AccountId;
+
+        fn new_owner() -> MockOwner {
+            let mut contract = MockOwner::new(Data::new());
+            contract.new_impl();
+            contract
+        }
+
+        #[test]
+        fn new_impl_works() {
+            MockEnv::run(|| {
+                let contract = new_owner();
+                assert_eq!(contract.owner(), &Some(MockEnv::caller()));
+            })
+        }
+
+        #[test]
+        fn transfer_ownership_works() {
+            MockEnv::run(|| {
+                let bob = AccountId::from([0x02; 32]);
+                let mut contract = new_owner();
+                contract.transfer_ownership(bob);
+                assert_eq!(contract.owner(), &Some(bob));
+
+                MockEnv::set_caller(bob);
+                contract.renounce_ownership();
+                assert_eq!(contract.owner(), &None);
+            })
+        }
+
+        #[test]
+        #[should_panic]
+        fn transfer_ownership_by_other_fails() {
+            MockEnv::run(|| {
+                let mut contract = new_owner();
+                MockEnv::set_caller(AccountId::from([0x02; 32]));
+                contract.transfer_ownership(AccountId::from([0x02; 32]));
+            })
+        }
+    }
+}
+
+pub use mock::MockOwner;
@@ -0,0 +1,175 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// A token receiver contract for testing the safe transfers of `Erc20`, `Erc721`
+/// and `Erc1155`.
+///
+/// The message selectors are the `ON_*_RECEIVED` constants of the `contract` crate.
+#[ink::contract]
+pub mod receiver_mock {
+    use ::ink_prelude::vec::Vec;
+
+    #[ink(storage)]
+    pub struct ReceiverMock {
+        /// Whether the callbacks reply the magic value accepting the tokens.
+        accept: bool,
+        /// Number of accepted callbacks.
+        received: u32,
+    }
+
+    impl ReceiverMock {
+        /// Creates a receiver which accepts tokens if `accept` is `true`.
+        #[ink(constructor)]
+        pub fn new(accept: bool) -> Self {
+            Self {
+                accept,
+                received: 0,
+            }
+        }
+
+        /// Creates a receiver accepting all tokens.
+        #[ink(constructor)]
+        pub fn default() -> Self {
+            Self::new(true)
+        }
+
+        /// Sets whether the callbacks accept the tokens.
+        #[ink(message)]
+        pub fn set_accept(&mut self, accept: bool) {
+            self.accept = accept;
+        }
+
+        /// Returns the number of accepted callbacks.
+        #[ink(message)]
+        pub fn received(&self) -> u32 {
+            self.received
+        }
+
+        /// Called by an ERC-20 token on `safe_transfer` and `safe_transfer_from`.
+        #[ink(message, selector = "0x88A7CA5C")]
+        pub fn on_erc20_received(
+            &mut self,
+            _operator: AccountId,
+            _from: AccountId,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> [u8; 4] {
+            self.reply(::contract::ON_ERC20_RECEIVED)
+        }
+
+        /// Called by an ERC-721 token on `safe_transfer_from`.
+        #[ink(message, selector = "0x150B7A02")]
+        pub fn on_erc721_received(
+            &mut self,
+            _operator: AccountId,
+            _from: AccountId,
+            _id: u32,
+            _data: Vec<u8>,
+        ) -> [u8; 4] {
+            self.reply(::contract::ON_ERC721_RECEIVED)
+        }
+
+        /// Called by an ERC-1155 token on `safe_transfer_from`.
+        #[ink(message, selector = "0xF23A6E61")]
+        pub fn on_erc1155_received(
+            &mut self,
+            _operator: AccountId,
+            _from: AccountId,
+            _id: u128,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> [u8; 4] {
+            self.reply(::contract::ON_ERC1155_RECEIVED)
+        }
+
+        /// Called by an ERC-1155 token on `safe_batch_transfer_from`.
+        #[ink(message, selector = "0xBC197C81")]
+        pub fn on_erc1155_batch_received(
+            &mut self,
+            _operator: AccountId,
+            _from: AccountId,
+            _ids: Vec<u128>,
+            _values: Vec<Balance>,
+            _data: Vec<u8>,
+        ) -> [u8; 4] {
+            self.reply(::contract::ON_ERC1155_BATCH_RECEIVED)
+        }
+
+        fn reply(&mut self, magic: [u8; 4]) -> [u8; 4] {
+            if !self.accept {
+                return [0x00; 4]
+            }
+            self.received += 1;
+            magic
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn accepting_receiver_replies_magic_values() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut receiver = ReceiverMock::default();
+
+            assert_eq!(
+                receiver.on_erc20_received(accounts.alice, accounts.alice, 10, Vec::new()),
+                ::contract::ON_ERC20_RECEIVED
+            );
+            assert_eq!(
+                receiver.on_erc721_received(accounts.alice, accounts.alice, 1, Vec::new()),
+                ::contract::ON_ERC721_RECEIVED
+            );
+            assert_eq!(
+                receiver.on_erc1155_received(
+                    accounts.alice,
+                    accounts.alice,
+                    1,
+                    10,
+                    Vec::new()
+                ),
+                ::contract::ON_ERC1155_RECEIVED
+            );
+            assert_eq!(
+                receiver.on_erc1155_batch_received(
+                    accounts.alice,
+                    accounts.alice,
+                    vec![1],
+                    vec![10],
+                    Vec::new()
+                ),
+                ::contract::ON_ERC1155_BATCH_RECEIVED
+            );
+            assert_eq!(receiver.received(), 4);
+        }
+
+        #[ink::test]
+        fn rejecting_receiver_replies_zero() {
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            let mut receiver = ReceiverMock::new(false);
+
+            assert_eq!(
+                receiver.on_erc721_received(accounts.alice, accounts.alice, 1, Vec::new()),
+                [0x00; 4]
+            );
+            assert_eq!(receiver.received(), 0);
+
+            receiver.set_accept(true);
+            assert_eq!(
+                receiver.on_erc721_received(accounts.alice, accounts.alice, 1, Vec::new()),
+                ::contract::ON_ERC721_RECEIVED
+            );
+            assert_eq!(receiver.received(), 1);
+        }
+    }
+}
@@ -0,0 +1,423 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// A factory deploying configured instances of the `Erc20` contract.
+#[ink::contract]
+pub mod token_factory {
+    use ::erc20_basic::{
+        selectors::NEW_WITH_METADATA,
+        Erc20Ref,
+    };
+    use ::ink_prelude::{
+        string::String,
+        vec::Vec,
+    };
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ::ink_storage::{
+        collections::{
+            HashMap as StorageHashMap,
+            Vec as StorageVec,
+        },
+        lazy::Lazy,
+    };
+    use ::ink_storage::traits::{
+        PackedLayout,
+        SpreadLayout,
+    };
+    use scale::Encode;
+
+    /// The most tokens returned by one call of `tokens_of`, so that a page stays
+    /// within the gas and the output size of a message.
+    pub const MAX_TOKENS_PAGE: u32 = 100;
+
+    /// A token deployed by the factory.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ::ink_storage::traits::StorageLayout)
+    )]
+    pub struct Deployment {
+        /// The token contract.
+        pub token: AccountId,
+        /// The account which created the token.
+        pub creator: AccountId,
+    }
+
+    #[ink(storage)]
+    pub struct TokenFactory {
+        /// Code hash of the `Erc20` contract.
+        erc20_code_hash: Lazy<Hash>,
+        /// All deployed tokens, in order of creation.
+        deployments: StorageVec<Deployment>,
+        /// Mapping from a creator and the number of one of its tokens to the index of
+        /// the token in `deployments`.
+        tokens_by_creator: StorageHashMap<(AccountId, u32), u32>,
+        /// Mapping from creator to the number of its tokens.
+        tokens_count_by_creator: StorageHashMap<AccountId, u32>,
+    }
+
+    /// Event emitted when a token is created.
+    #[ink(event)]
+    pub struct TokenCreated {
+        #[ink(topic)]
+        creator: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        index: u32,
+    }
+
+    /// The TokenFactory error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the token could not be instantiated.
+        InstantiateFailed,
+    }
+
+    /// The TokenFactory result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl TokenFactory {
+        /// Creates a factory deploying the `Erc20` code of `erc20_code_hash`.
+        #[ink(constructor)]
+        pub fn new(erc20_code_hash: Hash) -> Self {
+            Self {
+                erc20_code_hash: Lazy::new(erc20_code_hash),
+                deployments: StorageVec::new(),
+                tokens_by_creator: StorageHashMap::new(),
+                tokens_count_by_creator: StorageHashMap::new(),
+            }
+        }
+
+        /// Returns the code hash of the deployed `Erc20` contracts.
+        #[ink(message)]
+        pub fn erc20_code_hash(&self) -> Hash {
+            *self.erc20_code_hash
+        }
+
+        /// Instantiates an `Erc20` token with the metadata, giving the initial supply
+        /// to `owner`.
+        ///
+        /// The salt is derived from the caller and the number of tokens deployed, and
+        /// the transferred balance is the endowment of the token. On error it is
+        /// refunded.
+        ///
+        /// On success a `TokenCreated` event is emitted.
+        #[ink(message, payable)]
+        pub fn create_token(
+            &mut self,
+            name: String,
+            symbol: String,
+            decimals: u8,
+            initial_supply: Balance,
+            owner: AccountId,
+        ) -> Result<AccountId> {
+            let creator = self.env().caller();
+            let endowment = self.env().transferred_balance();
+            let index = self.deployments.len();
+            let salt = (creator, index).encode();
+            let result = ::contract::instantiate::<ink_env::DefaultEnvironment, _, _>(
+                *self.erc20_code_hash,
+                NEW_WITH_METADATA,
+                (Some(name), Some(symbol), decimals, initial_supply, owner),
+                endowment,
+                &salt,
+            )
+            .map(|token: Erc20Ref<ink_env::DefaultEnvironment>| token.account_id())
+            .map_err(|_| Error::InstantiateFailed);
+            let token = self.refund_on_error(creator, endowment, result)?;
+            self.register(creator, token);
+            Ok(token)
+        }
+
+        /// Returns the number of deployed tokens.
+        #[ink(message)]
+        pub fn tokens_count(&self) -> u32 {
+            self.deployments.len()
+        }
+
+        /// Returns the deployment at `index`, in order of creation.
+        #[ink(message)]
+        pub fn token_at(&self, index: u32) -> Option<Deployment> {
+            self.deployments.get(index).cloned()
+        }
+
+        /// Returns the number of tokens created by `creator`.
+        #[ink(message)]
+        pub fn tokens_count_of(&self, creator: AccountId) -> u32 {
+            self.tokens_count_by_creator
+                .get(&creator)
+                .copied()
+                .unwrap_or(0)
+        }
+
+        /// Returns at most `limit` tokens created by `creator`, in order of creation
+        /// from its `start`-th token. A page has at most `MAX_TOKENS_PAGE` tokens.
+        #[ink(message)]
+        pub fn tokens_of(
+            &self,
+            creator: AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<AccountId> {
+            let limit = limit.min(MAX_TOKENS_PAGE);
+            let end = start
+                .saturating_add(limit)
+                .min(self.tokens_count_of(creator));
+            (start..end)
+                .filter_map(|number| self.tokens_by_creator.get(&(creator, number)))
+                .filter_map(|index| self.deployments.get(*index))
+                .map(|deployment| deployment.token)
+                .collect()
+        }
+
+        /// Sends the transferred balance back to `caller` if `result` is an error, a
+        /// failed instantiation leaves the endowment in the factory.
+        fn refund_on_error<T>(
+            &mut self,
+            caller: AccountId,
+            value: Balance,
+            result: Result<T>,
+        ) -> Result<T> {
+            if result.is_err() && value > 0 {
+                self.env()
+                    .transfer(caller, value)
+                    .expect("factory does not hold the endowment");
+            }
+            result
+        }
+
+        /// Records the `token` created by `creator`.
+        fn register(&mut self, creator: AccountId, token: AccountId) {
+            let index = self.deployments.len();
+            self.deployments.push(Deployment { token, creator });
+            let number = self.tokens_count_of(creator);
+            self.tokens_by_creator.insert((creator, number), index);
+            self.tokens_count_by_creator.insert(creator, number + 1);
+            self.env().emit_event(TokenCreated {
+                creator,
+                token,
+                index,
+            });
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ::contract_test::{
+            assert_event,
+            balance_of,
+            contract_id,
+            default_accounts,
+            set_balance,
+            DefaultEnv,
+        };
+        #[cfg(feature = "test-utils")]
+        use ::contract_test::{
+            as_caller,
+            with_value,
+        };
+
+        use ink_lang as ink;
+
+        type Event = <TokenFactory as ::ink_lang::BaseEvent>::Type;
+
+        ::contract_test::event_topics!(
+            TokenCreated,
+            "TokenFactory::TokenCreated",
+            [creator, token]
+        );
+
+        #[ink::test]
+        fn new_works() {
+            let code_hash = Hash::from([0x42; 32]);
+            let factory = TokenFactory::new(code_hash);
+            assert_eq!(factory.erc20_code_hash(), code_hash);
+            assert_eq!(factory.tokens_count(), 0);
+            assert_eq!(factory.token_at(0), None);
+        }
+
+        #[ink::test]
+        fn registry_works() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut factory = TokenFactory::new(Hash::from([0x42; 32]));
+            let first = AccountId::from([0x10; 32]);
+            let second = AccountId::from([0x11; 32]);
+            let third = AccountId::from([0x12; 32]);
+
+            factory.register(accounts.alice, first);
+            factory.register(accounts.bob, second);
+            factory.register(accounts.alice, third);
+
+            assert_eq!(factory.tokens_count(), 3);
+            assert_eq!(
+                factory.token_at(1),
+                Some(Deployment {
+                    token: second,
+                    creator: accounts.bob,
+                })
+            );
+            assert_eq!(factory.tokens_count_of(accounts.alice), 2);
+            assert_eq!(factory.tokens_of(accounts.alice, 0, 10), vec![first, third]);
+            assert_eq!(factory.tokens_of(accounts.bob, 0, 10), vec![second]);
+            assert_eq!(factory.tokens_count_of(accounts.eve), 0);
+            assert_eq!(factory.tokens_of(accounts.eve, 0, 10), Vec::new());
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_event::<Event, _>(
+                &emitted_events[2],
+                TokenCreated {
+                    creator: accounts.alice,
+                    token: third,
+                    index: 2,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn tokens_of_is_paged() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut factory = TokenFactory::new(Hash::from([0x42; 32]));
+            let tokens = (0..MAX_TOKENS_PAGE + 5)
+                .map(|n| AccountId::from([n as u8; 32]))
+                .collect::<Vec<_>>();
+            for token in tokens.iter() {
+                factory.register(accounts.alice, *token);
+            }
+
+            assert_eq!(factory.tokens_of(accounts.alice, 1, 2), tokens[1..3].to_vec());
+            assert_eq!(
+                factory.tokens_of(accounts.alice, 0, u32::MAX),
+                tokens[..MAX_TOKENS_PAGE as usize].to_vec()
+            );
+            assert_eq!(
+                factory.tokens_of(accounts.alice, MAX_TOKENS_PAGE, u32::MAX),
+                tokens[MAX_TOKENS_PAGE as usize..].to_vec()
+            );
+            assert_eq!(factory.tokens_of(accounts.alice, u32::MAX, 10), Vec::new());
+        }
+
+        // Needs the stub of the instantiation.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn create_token_works() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut factory = TokenFactory::new(Hash::from([0x42; 32]));
+            let alice = accounts.alice;
+            ::contract::set_instantiator(move |code_hash, _, input, salt| {
+                assert_eq!(code_hash, Hash::from([0x42; 32]).encode());
+                let mut expected = NEW_WITH_METADATA.to_vec();
+                (
+                    Some(String::from("Token")),
+                    Some(String::from("TKN")),
+                    18u8,
+                    1000 as Balance,
+                    alice,
+                )
+                    .encode_to(&mut expected);
+                assert_eq!(input, expected);
+                // The new account is told apart by the index in the salt.
+                let (creator, index) =
+                    <(AccountId, u32) as scale::Decode>::decode(&mut &salt[..])
+                        .expect("Cannot decode salt");
+                assert_eq!(creator, alice);
+                Ok(AccountId::from([0x10 + index as u8; 32]).encode())
+            });
+            let create = |factory: &mut TokenFactory| {
+                factory.create_token(
+                    String::from("Token"),
+                    String::from("TKN"),
+                    18,
+                    1000,
+                    alice,
+                )
+            };
+
+            let first = AccountId::from([0x10; 32]);
+            let second = AccountId::from([0x11; 32]);
+            assert_eq!(create(&mut factory), Ok(first));
+            assert_eq!(create(&mut factory), Ok(second));
+            assert_eq!(factory.tokens_count(), 2);
+            assert_eq!(factory.tokens_of(alice, 0, 10), vec![first, second]);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            assert_event::<Event, _>(
+                &emitted_events[1],
+                TokenCreated {
+                    creator: alice,
+                    token: second,
+                    index: 1,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn failed_creation_refunds_endowment() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut factory = TokenFactory::new(Hash::from([0x42; 32]));
+            // The endowment is held by the factory during the call.
+            set_balance(contract_id(), 100);
+            let bob_balance = balance_of(accounts.bob);
+
+            assert_eq!(
+                factory.refund_on_error::<AccountId>(
+                    accounts.bob,
+                    100,
+                    Err(Error::InstantiateFailed)
+                ),
+                Err(Error::InstantiateFailed)
+            );
+            assert_eq!(balance_of(accounts.bob), bob_balance + 100);
+            assert_eq!(balance_of(contract_id()), 0);
+            assert_eq!(factory.tokens_count(), 0);
+        }
+
+        // Needs the stub of the instantiation.
+        #[cfg(feature = "test-utils")]
+        #[ink::test]
+        fn failed_create_token_refunds_endowment() {
+            let accounts = default_accounts::<DefaultEnv>();
+            let mut factory = TokenFactory::new(Hash::from([0x42; 32]));
+            ::contract::set_instantiator(|_, _, _, _| {
+                Err(::ink_env::Error::CalleeTrapped)
+            });
+            // The endowment is held by the factory during the call.
+            set_balance(contract_id(), 100);
+            let bob_balance = balance_of(accounts.bob);
+
+            assert_eq!(
+                as_caller(accounts.bob, || {
+                    with_value(100, || {
+                        factory.create_token(
+                            String::from("Token"),
+                            String::from("TKN"),
+                            18,
+                            1000,
+                            accounts.bob,
+                        )
+                    })
+                }),
+                Err(Error::InstantiateFailed)
+            );
+            assert_eq!(balance_of(accounts.bob), bob_balance + 100);
+            assert_eq!(balance_of(contract_id()), 0);
+            assert_eq!(factory.tokens_count(), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 0);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+import BN from 'bn.js';
+import { expect } from 'chai';
+import { patract, network } from 'redspot';
+
+const { getContractFactory, getRandomSigner } = patract;
+
+const { api, getSigners } = network;
+
+describe('Safe transfers', () => {
+  after(() => {
+    return api.disconnect();
+  });
+
+  async function setup() {
+    const one = new BN(10).pow(new BN(api.registry.chainDecimals[0]));
+    const signers = await getSigners();
+    const Alice = signers[0];
+    const sender = await getRandomSigner(Alice, one.muln(10000));
+
+    const erc721Factory = await getContractFactory('erc721', sender);
+    const erc721 = await erc721Factory.deploy('new');
+
+    const receiverFactory = await getContractFactory('receiver_mock', sender);
+    const accepting = await receiverFactory.deploy('new', true);
+    const rejecting = await receiverFactory.deploy('new', false);
+
+    return { sender, erc721, accepting, rejecting };
+  }
+
+  it('Receiver contract accepts token', async () => {
+    const { sender, erc721, accepting } = await setup();
+
+    await erc721.tx.mint(1);
+    await erc721.tx.safeTransferFrom(sender.address, accepting.address, 1, []);
+
+    const owner = await erc721.query.ownerOf(1);
+    expect(owner.output).to.equal(accepting.address);
+
+    const received = await accepting.query.received();
+    expect(received.output).to.equal(1);
+  });
+
+  it('Receiver contract rejects token', async () => {
+    const { sender, erc721, rejecting } = await setup();
+
+    await erc721.tx.mint(1);
+    const result = await erc721.query.safeTransferFrom(sender.address, rejecting.address, 1, []);
+    expect(result.output?.toJSON()).to.eql({ err: 'TransferRejected' });
+
+    const owner = await erc721.query.ownerOf(1);
+    expect(owner.output).to.equal(sender.address);
+  });
+});
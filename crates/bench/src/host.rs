@@ -0,0 +1,667 @@
+//! A host of the `seal0` API of `pallet-contracts`, the one ink! 3.0.0-rc3 imports,
+//! which counts what each call costs.
+//!
+//! The host keeps the storage of a contract and of the callees added to it. A call
+//! of a callee runs it in the same way and adds its cost to the cost of the caller,
+//! calls of other accounts fail with `NotCallable`. Transfers succeed without moving
+//! any balance.
+
+use blake2::{
+    digest::{
+        consts::{
+            U16,
+            U32,
+        },
+        Digest,
+    },
+    Blake2b,
+};
+use std::{
+    collections::BTreeMap,
+    convert::TryInto,
+    fmt,
+    ops::AddAssign,
+    rc::Rc,
+};
+use wasmi::{
+    core::{
+        HostError,
+        Trap,
+    },
+    Caller,
+    Config,
+    Engine,
+    ExternType,
+    Func,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    Value,
+};
+
+/// The account type of the contracts.
+pub type AccountId = [u8; 32];
+/// The balance type of the contracts.
+pub type Balance = u128;
+
+/// The account of the contract.
+pub const ADDRESS: AccountId = [0x10; 32];
+/// The native balance of the contract, returned by `seal_balance`.
+pub const CONTRACT_BALANCE: Balance = 1_000_000_000_000;
+
+/// The return code of a successful host function.
+const SUCCESS: i32 = 0;
+/// The return code of `seal_call` for a callee which trapped.
+const CALLEE_TRAPPED: i32 = 1;
+/// The return code of `seal_call` for a callee which reverted.
+const CALLEE_REVERTED: i32 = 2;
+/// The return code of `seal_get_storage` for an empty key.
+const KEY_NOT_FOUND: i32 = 3;
+/// The return code of `seal_call` for an account without a contract.
+const NOT_CALLABLE: i32 = 8;
+
+/// The revert flag of `seal_return`.
+const REVERT: u32 = 1;
+
+/// The output pointer of `seal_call` which skips the output.
+const SKIP_OUTPUT: u32 = u32::MAX;
+
+/// What a call cost.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Cost {
+    /// The executed Wasm instructions, measured as wasmi fuel.
+    pub instructions: u64,
+    /// The calls of `seal_get_storage`.
+    pub storage_reads: u32,
+    /// The calls of `seal_set_storage` and `seal_clear_storage`.
+    pub storage_writes: u32,
+    /// The bytes returned by `seal_get_storage`.
+    pub bytes_read: u32,
+    /// The bytes passed to `seal_set_storage`.
+    pub bytes_written: u32,
+    /// The calls of `seal_deposit_event`.
+    pub events: u32,
+    /// The calls of other contracts and the transfers of native balance.
+    pub calls: u32,
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, other: Self) {
+        self.instructions += other.instructions;
+        self.storage_reads += other.storage_reads;
+        self.storage_writes += other.storage_writes;
+        self.bytes_read += other.bytes_read;
+        self.bytes_written += other.bytes_written;
+        self.events += other.events;
+        self.calls += other.calls;
+    }
+}
+
+/// A call of a constructor or of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub caller: AccountId,
+    /// The transferred native balance.
+    pub value: Balance,
+    /// The call data, selector first.
+    pub input: Vec<u8>,
+}
+
+/// The outcome of a call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub cost: Cost,
+    /// The data returned by `seal_return`, empty if the contract returned nothing.
+    pub output: Vec<u8>,
+    /// Whether the call was reverted, by a trap, by the revert flag of `seal_return`
+    /// or by an error code of the dispatch.
+    pub reverted: bool,
+}
+
+/// The errors of the host.
+#[derive(Debug)]
+pub enum Error {
+    /// Returned if the binary is not a valid module or cannot be instantiated.
+    Wasm(wasmi::Error),
+    /// Returned if the contract does not export `deploy` or `call`.
+    MissingExport(&'static str),
+    /// Returned if no binary is given for a callee of a scenario.
+    MissingBinary(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Wasm(error) => write!(f, "{}", error),
+            Self::MissingExport(name) => write!(f, "the contract exports no {}", name),
+            Self::MissingBinary(name) => write!(f, "no binary of the callee {}", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl<E: Into<wasmi::Error>> From<E> for Error {
+    fn from(error: E) -> Self {
+        Self::Wasm(error.into())
+    }
+}
+
+/// The trap of `seal_return` and `seal_terminate`, which end the call.
+#[derive(Debug)]
+struct Exit;
+
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the contract returned")
+    }
+}
+
+impl HostError for Exit {}
+
+/// The state of the host during a call.
+struct State {
+    memory: Option<Memory>,
+    address: AccountId,
+    call: Call,
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    callees: BTreeMap<AccountId, Contract>,
+    cost: Cost,
+    output: Vec<u8>,
+    reverted: bool,
+    trapped: bool,
+}
+
+/// A deployed contract, with its storage and the contracts it can call.
+#[derive(Clone)]
+pub struct Contract {
+    engine: Engine,
+    module: Rc<Module>,
+    address: AccountId,
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    callees: BTreeMap<AccountId, Contract>,
+}
+
+impl Contract {
+    /// Loads the contract at `ADDRESS` from its Wasm binary, with an empty storage.
+    pub fn new(wasm: &[u8]) -> Result<Self, Error> {
+        Self::at(ADDRESS, wasm)
+    }
+
+    /// Loads the contract at `address` from its Wasm binary, with an empty storage.
+    pub fn at(address: AccountId, wasm: &[u8]) -> Result<Self, Error> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)?;
+        Ok(Self {
+            engine,
+            module: Rc::new(module),
+            address,
+            storage: BTreeMap::new(),
+            callees: BTreeMap::new(),
+        })
+    }
+
+    /// Adds `callee`, which the contract calls at its address.
+    pub fn add_callee(&mut self, callee: Contract) {
+        self.callees.insert(callee.address, callee);
+    }
+
+    /// Calls a constructor of the contract.
+    pub fn deploy(&mut self, call: &Call) -> Result<Outcome, Error> {
+        Ok(self.execute("deploy", call)?.0)
+    }
+
+    /// Calls a message of the contract.
+    pub fn call(&mut self, call: &Call) -> Result<Outcome, Error> {
+        Ok(self.execute("call", call)?.0)
+    }
+
+    /// Returns the storage of the contract.
+    pub fn storage(&self) -> &BTreeMap<Vec<u8>, Vec<u8>> {
+        &self.storage
+    }
+
+    /// Returns the callees of the contract, by address.
+    pub fn callees(&self) -> &BTreeMap<AccountId, Contract> {
+        &self.callees
+    }
+
+    /// Runs the export `name` in a new instance, like the chain does for every call,
+    /// and keeps the storage it and its callees leave unless the call is reverted.
+    ///
+    /// Returns the outcome and whether the call trapped.
+    fn execute(
+        &mut self,
+        name: &'static str,
+        call: &Call,
+    ) -> Result<(Outcome, bool), Error> {
+        let state = State {
+            memory: None,
+            address: self.address,
+            call: call.clone(),
+            storage: self.storage.clone(),
+            callees: self.callees.clone(),
+            cost: Cost::default(),
+            output: Vec::new(),
+            reverted: false,
+            trapped: false,
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.add_fuel(u64::MAX / 2)?;
+
+        let mut linker = Linker::new(&self.engine);
+        for import in self.module.imports() {
+            match import.ty() {
+                ExternType::Memory(ty) => {
+                    let memory = Memory::new(&mut store, *ty)?;
+                    store.data_mut().memory = Some(memory);
+                    linker.define(import.module(), import.name(), memory)?;
+                }
+                ExternType::Func(ty) => {
+                    let function = import.name().to_owned();
+                    let func = Func::new(
+                        &mut store,
+                        ty.clone(),
+                        move |caller, params, results| {
+                            host_call(&function, caller, params, results)
+                        },
+                    );
+                    linker.define(import.module(), import.name(), func)?;
+                }
+                // Contracts import nothing else, the instantiation fails on it.
+                _ => {}
+            }
+        }
+        let instance = linker
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)?;
+        if store.data().memory.is_none() {
+            store.data_mut().memory = instance.get_memory(&store, "memory");
+        }
+        let func = instance
+            .get_func(&store, name)
+            .ok_or(Error::MissingExport(name))?;
+        let mut results = vec![Value::I32(0); func.ty(&store).results().len()];
+
+        let fuel = store.fuel_consumed().unwrap_or_default();
+        let result = func.call(&mut store, &[], &mut results);
+        let instructions = store.fuel_consumed().unwrap_or_default() - fuel;
+
+        let mut state = store.into_data();
+        // The instructions of the callees are counted already.
+        state.cost.instructions += instructions;
+        match result {
+            // A non-zero return code is an error of the dispatch, e.g. an unknown
+            // selector.
+            Ok(()) => {
+                state.reverted =
+                    matches!(results.first(), Some(Value::I32(code)) if *code != 0)
+            }
+            Err(wasmi::Error::Trap(trap)) if trap.downcast_ref::<Exit>().is_some() => {}
+            Err(_) => {
+                state.reverted = true;
+                state.trapped = true;
+            }
+        }
+        if !state.reverted {
+            self.storage = state.storage;
+            self.callees = state.callees;
+        }
+        let outcome = Outcome {
+            cost: state.cost,
+            output: state.output,
+            reverted: state.reverted,
+        };
+        Ok((outcome, state.trapped))
+    }
+}
+
+/// Returns the `n`th parameter of a host function as a pointer or a length.
+fn arg(params: &[Value], n: usize) -> u32 {
+    match params.get(n) {
+        Some(Value::I32(value)) => *value as u32,
+        Some(Value::I64(value)) => *value as u32,
+        _ => 0,
+    }
+}
+
+fn memory(caller: &Caller<'_, State>) -> Result<Memory, Trap> {
+    caller
+        .data()
+        .memory
+        .ok_or_else(|| Trap::new("the contract has no memory"))
+}
+
+fn read(caller: &Caller<'_, State>, ptr: u32, len: u32) -> Result<Vec<u8>, Trap> {
+    let mut buffer = vec![0; len as usize];
+    memory(caller)?
+        .read(caller, ptr as usize, &mut buffer)
+        .map_err(|_| Trap::new("out of bounds read"))?;
+    Ok(buffer)
+}
+
+fn write(caller: &mut Caller<'_, State>, ptr: u32, data: &[u8]) -> Result<(), Trap> {
+    memory(caller)?
+        .write(caller, ptr as usize, data)
+        .map_err(|_| Trap::new("out of bounds write"))
+}
+
+/// Writes `data` to the buffer at `out_ptr`, whose capacity is read from and whose
+/// length is written to `out_len_ptr`.
+fn write_output(
+    caller: &mut Caller<'_, State>,
+    out_ptr: u32,
+    out_len_ptr: u32,
+    data: &[u8],
+) -> Result<(), Trap> {
+    let mut capacity = [0; 4];
+    capacity.copy_from_slice(&read(caller, out_len_ptr, 4)?);
+    if data.len() > u32::from_le_bytes(capacity) as usize {
+        return Err(Trap::new("the output buffer is too small"))
+    }
+    write(caller, out_ptr, data)?;
+    write(caller, out_len_ptr, &(data.len() as u32).to_le_bytes())
+}
+
+/// Runs `seal_call`, a message of a callee, and returns its return code.
+fn call_callee(caller: &mut Caller<'_, State>, params: &[Value]) -> Result<i32, Trap> {
+    let arg = |n| arg(params, n);
+    let callee = read(caller, arg(0), arg(1))?;
+    // The third parameter is the gas.
+    let value = read(caller, arg(3), arg(4))?;
+    let input = read(caller, arg(5), arg(6))?;
+    let value = value
+        .try_into()
+        .map(Balance::from_le_bytes)
+        .map_err(|_| Trap::new("the value is not a balance"))?;
+
+    let state = caller.data_mut();
+    state.cost.calls += 1;
+    let call = Call {
+        caller: state.address,
+        value,
+        input,
+    };
+    let contract = match callee
+        .try_into()
+        .ok()
+        .and_then(|callee: AccountId| state.callees.get_mut(&callee))
+    {
+        Some(contract) => contract,
+        None => return Ok(NOT_CALLABLE),
+    };
+    let (outcome, trapped) = contract
+        .execute("call", &call)
+        .map_err(|error| Trap::new(error.to_string()))?;
+    state.cost += outcome.cost;
+    if trapped {
+        return Ok(CALLEE_TRAPPED)
+    }
+    if arg(7) != SKIP_OUTPUT {
+        write_output(caller, arg(7), arg(8), &outcome.output)?;
+    }
+    Ok(if outcome.reverted {
+        CALLEE_REVERTED
+    } else {
+        SUCCESS
+    })
+}
+
+fn host_call(
+    function: &str,
+    mut caller: Caller<'_, State>,
+    params: &[Value],
+    results: &mut [Value],
+) -> Result<(), Trap> {
+    let arg = |n| arg(params, n);
+    let mut code = SUCCESS;
+    match function {
+        "seal_input" => {
+            let input = caller.data().call.input.clone();
+            write_output(&mut caller, arg(0), arg(1), &input)?;
+        }
+        "seal_return" => {
+            let output = read(&caller, arg(1), arg(2))?;
+            let state = caller.data_mut();
+            state.output = output;
+            state.reverted = arg(0) & REVERT != 0;
+            return Err(Exit.into())
+        }
+        "seal_terminate" => return Err(Exit.into()),
+        "seal_get_storage" => {
+            let key = read(&caller, arg(0), 32)?;
+            let state = caller.data_mut();
+            state.cost.storage_reads += 1;
+            match state.storage.get(&key).cloned() {
+                Some(value) => {
+                    state.cost.bytes_read += value.len() as u32;
+                    write_output(&mut caller, arg(1), arg(2), &value)?;
+                }
+                None => code = KEY_NOT_FOUND,
+            }
+        }
+        "seal_set_storage" => {
+            let key = read(&caller, arg(0), 32)?;
+            let value = read(&caller, arg(1), arg(2))?;
+            let state = caller.data_mut();
+            state.cost.storage_writes += 1;
+            state.cost.bytes_written += value.len() as u32;
+            state.storage.insert(key, value);
+        }
+        "seal_clear_storage" => {
+            let key = read(&caller, arg(0), 32)?;
+            let state = caller.data_mut();
+            state.cost.storage_writes += 1;
+            state.storage.remove(&key);
+        }
+        "seal_caller" => {
+            let account = caller.data().call.caller;
+            write_output(&mut caller, arg(0), arg(1), &account)?;
+        }
+        "seal_address" => {
+            let address = caller.data().address;
+            write_output(&mut caller, arg(0), arg(1), &address)?;
+        }
+        "seal_value_transferred" => {
+            let value = caller.data().call.value;
+            write_output(&mut caller, arg(0), arg(1), &value.to_le_bytes())?;
+        }
+        "seal_balance" => {
+            let balance = CONTRACT_BALANCE.to_le_bytes();
+            write_output(&mut caller, arg(0), arg(1), &balance)?;
+        }
+        "seal_minimum_balance" | "seal_tombstone_deposit" | "seal_rent_allowance" => {
+            write_output(&mut caller, arg(0), arg(1), &0u128.to_le_bytes())?;
+        }
+        // The first parameter is the gas.
+        "seal_weight_to_fee" => {
+            write_output(&mut caller, arg(1), arg(2), &0u128.to_le_bytes())?;
+        }
+        "seal_now" => write_output(&mut caller, arg(0), arg(1), &0u64.to_le_bytes())?,
+        "seal_block_number" => {
+            write_output(&mut caller, arg(0), arg(1), &1u32.to_le_bytes())?;
+        }
+        "seal_gas_left" => {
+            write_output(&mut caller, arg(0), arg(1), &u64::MAX.to_le_bytes())?;
+        }
+        "seal_random" => write_output(&mut caller, arg(2), arg(3), &[0; 32])?,
+        "seal_hash_blake2_256" => {
+            let input = read(&caller, arg(0), arg(1))?;
+            write(&mut caller, arg(2), &Blake2b::<U32>::digest(&input))?;
+        }
+        "seal_hash_blake2_128" => {
+            let input = read(&caller, arg(0), arg(1))?;
+            write(&mut caller, arg(2), &Blake2b::<U16>::digest(&input))?;
+        }
+        "seal_deposit_event" => caller.data_mut().cost.events += 1,
+        "seal_call" => code = call_callee(&mut caller, params)?,
+        "seal_transfer" => caller.data_mut().cost.calls += 1,
+        "seal_println" | "seal_set_rent_allowance" => {}
+        _ => {
+            return Err(Trap::new(format!(
+                "the host function {} is not supported",
+                function
+            )))
+        }
+    }
+    if let Some(result) = results.first_mut() {
+        *result = Value::I32(code);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stores its input under the zero key and returns it, with the flags of its
+    /// first byte.
+    const ECHO: &str = r#"
+        (module
+            (import "seal0" "seal_input" (func $input (param i32 i32)))
+            (import "seal0" "seal_set_storage" (func $set (param i32 i32 i32)))
+            (import "seal0" "seal_return" (func $return (param i32 i32 i32)))
+            (import "seal0" "seal_random" (func $random (param i32 i32 i32 i32)))
+            (import "env" "memory" (memory 1 1))
+            (data (i32.const 256) "\40\00\00\00")
+            (func (export "deploy") (result i32)
+                (call $random (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0))
+                (i32.const 0))
+            (func (export "call") (result i32)
+                (call $input (i32.const 512) (i32.const 256))
+                (call $set (i32.const 0) (i32.const 512) (i32.load (i32.const 256)))
+                (call $return
+                    (i32.load8_u (i32.const 512))
+                    (i32.const 512)
+                    (i32.load (i32.const 256)))
+                (i32.const 0)))
+    "#;
+
+    /// Calls the contract at `CALLEE` with its input and returns the return code of
+    /// the call followed by the output of the callee.
+    const CALLER: &str = r#"
+        (module
+            (import "seal0" "seal_input" (func $input (param i32 i32)))
+            (import "seal0" "seal_call"
+                (func $call (param i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+            (import "seal0" "seal_return" (func $return (param i32 i32 i32)))
+            (import "env" "memory" (memory 1 1))
+            (data (i32.const 0) "\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07")
+            (data (i32.const 16) "\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07\07")
+            (data (i32.const 256) "\40\00\00\00\40\00\00\00")
+            (func (export "deploy") (result i32)
+                (i32.const 0))
+            (func (export "call") (result i32)
+                (call $input (i32.const 512) (i32.const 256))
+                (i32.store (i32.const 764)
+                    (call $call
+                        (i32.const 0) (i32.const 32)
+                        (i64.const 0)
+                        (i32.const 32) (i32.const 16)
+                        (i32.const 512) (i32.load (i32.const 256))
+                        (i32.const 768) (i32.const 260)))
+                (call $return
+                    (i32.const 0)
+                    (i32.const 764)
+                    (i32.add (i32.const 4) (i32.load (i32.const 260))))
+                (i32.const 0)))
+    "#;
+
+    const CALLEE: AccountId = [0x07; 32];
+
+    fn call(input: Vec<u8>) -> Call {
+        Call {
+            caller: [0x01; 32],
+            value: 0,
+            input,
+        }
+    }
+
+    #[test]
+    fn call_works() {
+        let wasm = wat::parse_str(ECHO).expect("Cannot parse the module");
+        let mut contract = Contract::new(&wasm).expect("Cannot load the module");
+
+        let outcome = contract.call(&call(vec![0x00, 0xaa])).expect("Cannot call");
+        assert_eq!(outcome.output, [0x00, 0xaa]);
+        assert!(!outcome.reverted);
+        assert_eq!(outcome.cost.storage_writes, 1);
+        assert_eq!(outcome.cost.bytes_written, 2);
+        assert!(outcome.cost.instructions > 0);
+        assert_eq!(
+            contract.storage().get(&[0; 32][..]),
+            Some(&vec![0x00, 0xaa])
+        );
+
+        // The revert flag drops the write.
+        let outcome = contract.call(&call(vec![0x01, 0xbb])).expect("Cannot call");
+        assert_eq!(outcome.output, [0x01, 0xbb]);
+        assert!(outcome.reverted);
+        assert_eq!(
+            contract.storage().get(&[0; 32][..]),
+            Some(&vec![0x00, 0xaa])
+        );
+
+        // The input does not fit the buffer of the contract.
+        let outcome = contract.call(&call(vec![0x00; 65])).expect("Cannot call");
+        assert!(outcome.reverted);
+    }
+
+    #[test]
+    fn call_runs_callee() {
+        let wasm = wat::parse_str(CALLER).expect("Cannot parse the module");
+        let mut contract = Contract::new(&wasm).expect("Cannot load the module");
+
+        // No contract is deployed at `CALLEE`.
+        let outcome = contract.call(&call(vec![0x00, 0xaa])).expect("Cannot call");
+        assert_eq!(outcome.output[..4], NOT_CALLABLE.to_le_bytes());
+        assert_eq!(outcome.cost.calls, 1);
+
+        let wasm = wat::parse_str(ECHO).expect("Cannot parse the module");
+        contract.add_callee(Contract::at(CALLEE, &wasm).expect("Cannot load the module"));
+        let outcome = contract.call(&call(vec![0x00, 0xaa])).expect("Cannot call");
+        assert_eq!(outcome.output[..4], SUCCESS.to_le_bytes());
+        assert_eq!(outcome.output[4..], [0x00, 0xaa]);
+        assert!(!outcome.reverted);
+        // The cost of the callee is added to the cost of the call.
+        assert_eq!(outcome.cost.calls, 1);
+        assert_eq!(outcome.cost.storage_writes, 1);
+        let stored = |contract: &Contract| {
+            contract.callees()[&CALLEE]
+                .storage()
+                .get(&[0; 32][..])
+                .cloned()
+        };
+        assert_eq!(stored(&contract), Some(vec![0x00, 0xaa]));
+
+        // The revert of the callee drops its write, the caller handles it.
+        let outcome = contract.call(&call(vec![0x01, 0xbb])).expect("Cannot call");
+        assert_eq!(outcome.output[..4], CALLEE_REVERTED.to_le_bytes());
+        assert_eq!(outcome.output[4..], [0x01, 0xbb]);
+        assert!(!outcome.reverted);
+        assert_eq!(stored(&contract), Some(vec![0x00, 0xaa]));
+    }
+
+    #[test]
+    fn output_buffer_is_checked() {
+        let wasm = wat::parse_str(ECHO).expect("Cannot parse the module");
+        let mut contract = Contract::new(&wasm).expect("Cannot load the module");
+        // `seal_random` gets a buffer of no capacity.
+        let outcome = contract.deploy(&call(vec![])).expect("Cannot deploy");
+        assert!(outcome.reverted);
+    }
+
+    #[test]
+    fn missing_export_is_an_error() {
+        let wasm = wat::parse_str(r#"(module (memory (export "memory") 1))"#)
+            .expect("Cannot parse the module");
+        let mut contract = Contract::new(&wasm).expect("Cannot load the module");
+        assert!(matches!(
+            contract.call(&call(vec![])),
+            Err(Error::MissingExport("call"))
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! Benchmarks the messages of the contracts on a local Wasm executor, to compare
+//! what the modular `contract::Module` design costs against monolithic code.
+//!
+//! Every scenario deploys its contract and calls its messages in order, each call
+//! in a new instance like on chain. The host counts the executed instructions, the
+//! storage reads and writes, the events and the calls out of the contract.
+
+mod host;
+mod report;
+mod scenario;
+
+pub use host::{
+    AccountId,
+    Balance,
+    Call,
+    Contract,
+    Cost,
+    Error,
+    Outcome,
+    ADDRESS,
+};
+pub use report::{
+    comparison,
+    table,
+};
+pub use scenario::{
+    erc20_scenario,
+    erc20swap_scenario,
+    owner_scenario,
+    scenarios,
+    selector,
+    Message,
+    Scenario,
+    ALICE,
+    BOB,
+    CHARLIE,
+    INITIAL_SUPPLY,
+};
+
+/// The outcome of a benchmarked call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub contract: &'static str,
+    /// The label of the message, the constructor is labelled by its name too.
+    pub message: &'static str,
+    pub outcome: Outcome,
+}
+
+/// Runs `scenario` on the contract of `wasm` and returns the measurement of each
+/// call, the constructor first.
+///
+/// `callees` are the binaries of `scenario.callee_contracts()`, in order.
+pub fn run(
+    scenario: &Scenario,
+    wasm: &[u8],
+    callees: &[Vec<u8>],
+) -> Result<Vec<Measurement>, Error> {
+    let mut contract = Contract::new(wasm)?;
+    let mut binaries = callees.iter();
+    for (address, callee) in &scenario.callees {
+        contract.add_callee(deploy_callee(*address, callee, &mut binaries)?);
+    }
+    let measure = |message: &Message, outcome| {
+        Measurement {
+            contract: scenario.contract,
+            message: message.label,
+            outcome,
+        }
+    };
+
+    let mut measurements = vec![measure(
+        &scenario.constructor,
+        contract.deploy(&scenario.constructor.call)?,
+    )];
+    for message in &scenario.messages {
+        measurements.push(measure(message, contract.call(&message.call)?));
+    }
+    Ok(measurements)
+}
+
+/// Deploys the callee `scenario` at `address` with its own callees, taking the
+/// binaries from `binaries`, and makes its calls.
+fn deploy_callee<'a>(
+    address: AccountId,
+    scenario: &Scenario,
+    binaries: &mut impl Iterator<Item = &'a Vec<u8>>,
+) -> Result<Contract, Error> {
+    let wasm = binaries
+        .next()
+        .ok_or(Error::MissingBinary(scenario.contract))?;
+    let mut contract = Contract::at(address, wasm)?;
+    for (address, callee) in &scenario.callees {
+        contract.add_callee(deploy_callee(*address, callee, binaries)?);
+    }
+    contract.deploy(&scenario.constructor.call)?;
+    for message in &scenario.messages {
+        contract.call(&message.call)?;
+    }
+    Ok(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_build::{
+        contracts_dir,
+        read_wasm,
+    };
+
+    /// The ERC-20 example built by ink! 3.0.0-rc3, committed with its metadata.
+    fn erc20_example() -> Vec<u8> {
+        let bundle =
+            contracts_dir().join("cross-calling/call-by-dep/artifacts/erc20.contract");
+        read_wasm(&bundle).expect("Cannot read the bundle")
+    }
+
+    #[test]
+    fn erc20_scenario_runs() {
+        let measurements = run(&erc20_scenario("erc20"), &erc20_example(), &[])
+            .expect("Cannot run the scenario");
+        let labels = measurements.iter().map(|m| m.message).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [
+                "new",
+                "total_supply",
+                "balance_of",
+                "transfer",
+                "approve",
+                "allowance",
+                "transfer_from"
+            ]
+        );
+        for measurement in &measurements {
+            assert!(!measurement.outcome.reverted, "{:?}", measurement);
+            assert!(measurement.outcome.cost.instructions > 0);
+        }
+
+        let output = |message| {
+            &measurements
+                .iter()
+                .find(|m| m.message == message)
+                .expect("Cannot find the message")
+                .outcome
+        };
+        assert_eq!(output("total_supply").output, INITIAL_SUPPLY.to_le_bytes());
+        // `Ok(())`
+        assert_eq!(output("transfer").output, [0x00]);
+        assert_eq!(output("allowance").output, 50u128.to_le_bytes());
+        assert_eq!(output("transfer").cost.events, 1);
+        assert_eq!(output("balance_of").cost.storage_writes, 0);
+        assert!(output("transfer_from").cost.storage_writes > 0);
+    }
+
+    #[test]
+    fn callees_need_binaries() {
+        let error = run(&erc20swap_scenario(), &erc20_example(), &[erc20_example()])
+            .expect_err("Cannot run without the binaries of all the callees");
+        assert!(
+            matches!(error, Error::MissingBinary("erc20swap")),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn reverted_call_keeps_storage() {
+        let mut contract = Contract::new(&erc20_example()).expect("Cannot load");
+        let scenario = erc20_scenario("erc20");
+        contract
+            .deploy(&scenario.constructor.call)
+            .expect("Cannot deploy");
+        let storage = contract.storage().clone();
+
+        // Bob has no tokens, the transfer returns an error without reverting.
+        let transfer = Message::new("transfer", (CHARLIE, 10 as Balance), BOB, 0);
+        let outcome = contract.call(&transfer.call).expect("Cannot call");
+        assert!(!outcome.reverted);
+        assert_eq!(outcome.output, [0x01, 0x00]);
+
+        // An unknown selector is an error of the dispatch.
+        let unknown = Message::new("unknown", (), ALICE, 0);
+        let outcome = contract.call(&unknown.call).expect("Cannot call");
+        assert!(outcome.reverted);
+        assert_eq!(contract.storage(), &storage);
+    }
+}
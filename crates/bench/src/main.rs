@@ -0,0 +1,61 @@
+//! Builds the benchmarked contracts and prints what each of their messages costs.
+//!
+//! ```text
+//! cargo run -p bench -- [--contract <name>]... [--wasm <name>=<path>]...
+//! ```
+//!
+//! `--contract` picks the scenarios to run, all of them by default. `--wasm` takes
+//! the binary of a contract from a `.wasm` file or a `.contract` bundle instead of
+//! building it.
+
+use bench::{
+    comparison,
+    run,
+    scenarios,
+    table,
+};
+use contract_build::{
+    Args,
+    MODULAR,
+    MONOLITHIC,
+};
+use std::{
+    error::Error,
+    process,
+};
+
+const USAGE: &str = "usage: bench [--contract <name>]... [--wasm <name>=<path>]...";
+
+fn main() {
+    let args = Args::parse(USAGE, |_, _| Ok(false)).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(2)
+    });
+    if let Err(error) = bench(&args) {
+        eprintln!("error: {}", error);
+        process::exit(1)
+    }
+}
+
+fn bench(args: &Args) -> Result<(), Box<dyn Error>> {
+    let mut measurements = Vec::new();
+    for scenario in scenarios() {
+        if !args.picks(scenario.contract) {
+            continue
+        }
+        let wasm = args.binary(scenario.contract)?;
+        let callees = scenario
+            .callee_contracts()
+            .into_iter()
+            .map(|contract| args.binary(contract))
+            .collect::<Result<Vec<_>, _>>()?;
+        measurements.extend(run(&scenario, &wasm, &callees)?);
+    }
+
+    println!("{}", table(&measurements));
+    let compared = |name| measurements.iter().any(|m| m.contract == name);
+    if compared(MODULAR) && compared(MONOLITHIC) {
+        println!("{}", comparison(&measurements, MODULAR, MONOLITHIC));
+    }
+    Ok(())
+}
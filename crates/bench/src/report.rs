@@ -0,0 +1,127 @@
+//! Tables of the measurements, in Markdown.
+
+use crate::Measurement;
+use std::fmt::Write;
+
+/// Returns a table of the cost of every call of `measurements`.
+pub fn table(measurements: &[Measurement]) -> String {
+    let mut table = String::from(
+        "| contract | message | instructions | storage reads | storage writes \
+         | bytes read | bytes written | events | calls | reverted |\n\
+         |---|---|--:|--:|--:|--:|--:|--:|--:|---|\n",
+    );
+    for measurement in measurements {
+        let cost = &measurement.outcome.cost;
+        writeln!(
+            table,
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+            measurement.contract,
+            measurement.message,
+            cost.instructions,
+            cost.storage_reads,
+            cost.storage_writes,
+            cost.bytes_read,
+            cost.bytes_written,
+            cost.events,
+            cost.calls,
+            if measurement.outcome.reverted {
+                "yes"
+            } else {
+                "no"
+            },
+        )
+        .expect("Cannot write to a string");
+    }
+    table
+}
+
+/// Returns a table comparing the instructions of the messages both `base` and
+/// `other` have, with the overhead of `base` relative to `other`.
+pub fn comparison(measurements: &[Measurement], base: &str, other: &str) -> String {
+    let mut table = format!(
+        "| message | {} | {} | overhead |\n|---|--:|--:|--:|\n",
+        base, other
+    );
+    let find = |contract: &str, message: &str| {
+        measurements
+            .iter()
+            .find(|measurement| {
+                measurement.contract == contract && measurement.message == message
+            })
+            .map(|measurement| measurement.outcome.cost.instructions)
+    };
+    for measurement in measurements.iter().filter(|m| m.contract == base) {
+        let base_instructions = measurement.outcome.cost.instructions;
+        let other_instructions = match find(other, measurement.message) {
+            Some(instructions) => instructions,
+            None => continue,
+        };
+        let overhead = (base_instructions as f64 - other_instructions as f64)
+            / other_instructions.max(1) as f64
+            * 100.0;
+        writeln!(
+            table,
+            "| {} | {} | {} | {:+.1}% |",
+            measurement.message, base_instructions, other_instructions, overhead
+        )
+        .expect("Cannot write to a string");
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Cost,
+        Outcome,
+    };
+
+    fn measurement(
+        contract: &'static str,
+        message: &'static str,
+        instructions: u64,
+    ) -> Measurement {
+        Measurement {
+            contract,
+            message,
+            outcome: Outcome {
+                cost: Cost {
+                    instructions,
+                    storage_reads: 2,
+                    ..Cost::default()
+                },
+                output: Vec::new(),
+                reverted: false,
+            },
+        }
+    }
+
+    #[test]
+    fn table_works() {
+        let table = table(&[measurement("erc20", "transfer", 1200)]);
+        assert_eq!(
+            table.lines().last(),
+            Some("| erc20 | transfer | 1200 | 2 | 0 | 0 | 0 | 0 | 0 | no |")
+        );
+    }
+
+    #[test]
+    fn comparison_works() {
+        let measurements = [
+            measurement("erc20", "new", 1500),
+            measurement("erc20", "transfer", 1200),
+            measurement("erc20", "holders", 700),
+            measurement("erc20owner", "new", 1000),
+            measurement("erc20owner", "transfer", 1200),
+        ];
+        let comparison = comparison(&measurements, "erc20", "erc20owner");
+        assert_eq!(
+            comparison.lines().skip(2).collect::<Vec<_>>(),
+            vec![
+                "| new | 1500 | 1000 | +50.0% |",
+                "| transfer | 1200 | 1200 | +0.0% |"
+            ]
+        );
+    }
+}
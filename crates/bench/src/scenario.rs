@@ -0,0 +1,260 @@
+//! The calls benchmarked for each contract, in the order they are made.
+
+use crate::host::{
+    AccountId,
+    Balance,
+    Call,
+    ADDRESS,
+};
+use blake2::{
+    digest::{
+        consts::U32,
+        Digest,
+    },
+    Blake2b,
+};
+use scale::Encode;
+
+/// The default accounts of the off-chain tests, Alice deploys the contracts.
+pub const ALICE: AccountId = [0x01; 32];
+pub const BOB: AccountId = [0x02; 32];
+pub const CHARLIE: AccountId = [0x03; 32];
+
+/// The initial supply of the tokens, minted to Alice.
+pub const INITIAL_SUPPLY: Balance = 1_000_000;
+
+/// Returns the selector ink! derives from the name of a message, the first bytes of
+/// its BLAKE2 hash.
+///
+/// The pinned selectors of the ERC-20 and owner messages are these too.
+pub fn selector(name: &str) -> [u8; 4] {
+    let hash = Blake2b::<U32>::digest(name.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// A benchmarked call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// The name of the message, shared by the contracts to compare.
+    pub label: &'static str,
+    pub call: Call,
+}
+
+impl Message {
+    /// Returns a call of `label` by `caller` with `args`, transferring `value`.
+    pub fn new<Args: Encode>(
+        label: &'static str,
+        args: Args,
+        caller: AccountId,
+        value: Balance,
+    ) -> Self {
+        Self::with_selector(label, selector(label), args, caller, value)
+    }
+
+    /// Returns a call of `label` like `new`, for a message with a pinned selector
+    /// other than the one derived from its name.
+    pub fn with_selector<Args: Encode>(
+        label: &'static str,
+        selector: [u8; 4],
+        args: Args,
+        caller: AccountId,
+        value: Balance,
+    ) -> Self {
+        let mut input = selector.to_vec();
+        args.encode_to(&mut input);
+        Self {
+            label,
+            call: Call {
+                caller,
+                value,
+                input,
+            },
+        }
+    }
+}
+
+/// The calls benchmarked on a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    /// The name of the contract, see `contract_build::CONTRACTS`.
+    pub contract: &'static str,
+    pub constructor: Message,
+    pub messages: Vec<Message>,
+    /// The contracts called by the contract, deployed at their addresses and set up
+    /// by the calls of their scenarios before the contract, which are not measured.
+    pub callees: Vec<(AccountId, Scenario)>,
+}
+
+impl Scenario {
+    /// Returns the contracts of the callees, each followed by its own callees.
+    pub fn callee_contracts(&self) -> Vec<&'static str> {
+        self.callees
+            .iter()
+            .flat_map(|(_, callee)| {
+                std::iter::once(callee.contract).chain(callee.callee_contracts())
+            })
+            .collect()
+    }
+}
+
+/// Returns the scenarios of all the benchmarked contracts.
+pub fn scenarios() -> Vec<Scenario> {
+    vec![
+        erc20_scenario("erc20"),
+        erc20_scenario("erc20owner"),
+        owner_scenario(),
+        erc20swap_scenario(),
+    ]
+}
+
+/// Returns the scenario of the standard ERC-20 messages, shared by the modular
+/// `Erc20` and the monolithic `Erc20Ownable` to compare them.
+pub fn erc20_scenario(contract: &'static str) -> Scenario {
+    Scenario {
+        contract,
+        constructor: Message::new("new", INITIAL_SUPPLY, ALICE, 0),
+        messages: vec![
+            Message::new("total_supply", (), ALICE, 0),
+            Message::new("balance_of", ALICE, ALICE, 0),
+            Message::new("transfer", (BOB, 100 as Balance), ALICE, 0),
+            Message::new("approve", (BOB, 50 as Balance), ALICE, 0),
+            Message::new("allowance", (ALICE, BOB), ALICE, 0),
+            Message::new("transfer_from", (ALICE, CHARLIE, 50 as Balance), BOB, 0),
+        ],
+        callees: Vec::new(),
+    }
+}
+
+/// Returns the scenario of the `Owner` contract, ownership moves from Alice to Bob
+/// who renounces it.
+pub fn owner_scenario() -> Scenario {
+    Scenario {
+        contract: "owner",
+        constructor: Message::new("new", (), ALICE, 0),
+        messages: vec![
+            Message::new("get_owner", (), ALICE, 0),
+            Message::new("transfer_ownership", BOB, ALICE, 0),
+            Message::new("renounce_ownership", (), BOB, 0),
+        ],
+        callees: Vec::new(),
+    }
+}
+
+/// Returns the scenario of `ERC20Swap`, its token is an `Erc20` in which Alice
+/// approved the pool.
+///
+/// The token-to-token swap buys the tokens of a second pool, which Alice funded.
+pub fn erc20swap_scenario() -> Scenario {
+    let token: AccountId = [0x07; 32];
+    let other_pool: AccountId = [0x08; 32];
+    let other_token: AccountId = [0x09; 32];
+    let other_pool_scenario = Scenario {
+        contract: "erc20swap",
+        constructor: Message::new("new", other_token, ALICE, 0),
+        messages: vec![add_liquidity()],
+        callees: vec![(other_token, approved_token_scenario(other_pool))],
+    };
+    Scenario {
+        contract: "erc20swap",
+        constructor: Message::new("new", token, ALICE, 0),
+        messages: vec![
+            Message::new("token", (), ALICE, 0),
+            Message::new("reserves", (), ALICE, 0),
+            Message::new("get_amount_out", (1000 as Balance, true), ALICE, 0),
+            Message::new("set_token", token, ALICE, 0),
+            Message::new("total_supply", (), ALICE, 0),
+            Message::new("approve", (BOB, 50 as Balance), ALICE, 0),
+            add_liquidity(),
+            Message::new("token_balance_of", ALICE, ALICE, 0),
+            // Native balance for tokens.
+            Message::new("swap_exact_in", (0 as Balance, 0 as Balance), ALICE, 1000),
+            // Tokens for native balance, paid out to Bob.
+            Message::with_selector(
+                "swap_exact_in_to",
+                SWAP_EXACT_IN_TO,
+                (100 as Balance, 0 as Balance, BOB),
+                ALICE,
+                0,
+            ),
+            Message::new(
+                "swap_token_to_token",
+                (100 as Balance, 0 as Balance, other_pool),
+                ALICE,
+                0,
+            ),
+            Message::new(
+                "remove_liquidity",
+                (1000 as Balance, 0 as Balance, 0 as Balance),
+                ALICE,
+                0,
+            ),
+        ],
+        callees: vec![
+            (token, approved_token_scenario(ADDRESS)),
+            (other_pool, other_pool_scenario),
+        ],
+    }
+}
+
+/// The pinned selector of `ERC20Swap::swap_exact_in_to`.
+const SWAP_EXACT_IN_TO: [u8; 4] = [0x5a, 0x3c, 0x0f, 0x21];
+
+/// Returns the call of Alice adding 10000 of native balance and 1000 tokens to a
+/// pool, the first liquidity sets the price.
+fn add_liquidity() -> Message {
+    Message::new(
+        "add_liquidity",
+        (1000 as Balance, 0 as Balance),
+        ALICE,
+        10000,
+    )
+}
+
+/// Returns the scenario of an `Erc20` in which Alice approved `pool` for all her
+/// tokens.
+fn approved_token_scenario(pool: AccountId) -> Scenario {
+    let mut scenario = erc20_scenario("erc20");
+    scenario.messages = vec![Message::new("approve", (pool, INITIAL_SUPPLY), ALICE, 0)];
+    scenario
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selector_works() {
+        // The selectors of the committed metadata of the ERC-20 example.
+        assert_eq!(selector("new"), [0x9b, 0xae, 0x9d, 0x5e]);
+        assert_eq!(selector("total_supply"), [0xdb, 0x63, 0x75, 0xa8]);
+        assert_eq!(selector("transfer_from"), [0x0b, 0x39, 0x6f, 0x18]);
+        // The pinned selectors of `owner::selectors`.
+        assert_eq!(selector("get_owner"), [0x07, 0xfc, 0xd0, 0xb1]);
+        assert_eq!(selector("transfer_ownership"), [0x10, 0x7e, 0x33, 0xea]);
+        assert_eq!(selector("renounce_ownership"), [0x8c, 0x90, 0x06, 0x5b]);
+    }
+
+    #[test]
+    fn message_encodes_call_data() {
+        let message = Message::new("transfer", (BOB, 100 as Balance), ALICE, 0);
+        let input = &message.call.input;
+        assert_eq!(input.len(), 4 + 32 + 16);
+        assert_eq!(input[..4], selector("transfer"));
+        assert_eq!(input[4..36], BOB);
+        assert_eq!(input[36..], 100u128.to_le_bytes());
+
+        let message =
+            Message::with_selector("swap_exact_in_to", SWAP_EXACT_IN_TO, (), ALICE, 0);
+        assert_eq!(message.label, "swap_exact_in_to");
+        assert_eq!(message.call.input, SWAP_EXACT_IN_TO);
+    }
+
+    #[test]
+    fn callee_contracts_are_depth_first() {
+        assert_eq!(
+            erc20swap_scenario().callee_contracts(),
+            ["erc20", "erc20swap", "erc20"]
+        );
+        assert!(erc20_scenario("erc20").callee_contracts().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+//! The baseline of the sizes and the differences with it.
+
+use crate::Size;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+};
+
+/// The sizes of the contracts, by name, as stored in the baseline file.
+pub type Sizes = BTreeMap<String, Size>;
+
+/// Reads the sizes stored in `path`.
+pub fn load(path: &Path) -> Result<Sizes, crate::Error> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// Stores `sizes` in `path`, as pretty JSON to keep its diffs readable.
+pub fn save(path: &Path, sizes: &Sizes) -> Result<(), crate::Error> {
+    let mut json = serde_json::to_string_pretty(sizes)?;
+    json.push('\n');
+    Ok(fs::write(path, json)?)
+}
+
+/// The change of the size of a contract since the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub contract: String,
+    /// The total size in the baseline, `None` for a new contract.
+    pub base: Option<u64>,
+    pub current: u64,
+    /// The functions whose size changed, the largest changes first.
+    pub functions: Vec<(String, i64)>,
+}
+
+impl Change {
+    /// Returns the growth of the contract in percent of its baseline, `None` for a
+    /// new contract.
+    pub fn growth(&self) -> Option<f64> {
+        self.base
+            .map(|base| (self.current as f64 - base as f64) / base.max(1) as f64 * 100.0)
+    }
+
+    /// Returns whether the contract grew by more than `threshold` percent.
+    pub fn exceeds(&self, threshold: f64) -> bool {
+        self.growth().is_some_and(|growth| growth > threshold)
+    }
+}
+
+/// Returns the change of every contract of `current` since `base`.
+///
+/// The contracts of `base` which were not measured are left out, so that a run on
+/// some of the contracts can be checked against the full baseline.
+pub fn diff(base: &Sizes, current: &Sizes) -> Vec<Change> {
+    current
+        .iter()
+        .map(|(contract, size)| {
+            let base = base.get(contract);
+            Change {
+                contract: contract.clone(),
+                base: base.map(|base| base.total),
+                current: size.total,
+                functions: base
+                    .map_or_else(Vec::new, |base| function_changes(base, size)),
+            }
+        })
+        .collect()
+}
+
+fn function_changes(base: &Size, current: &Size) -> Vec<(String, i64)> {
+    let size = |size: &Size, name| size.functions.get(name).copied().unwrap_or(0) as i64;
+    let mut names: Vec<_> = base.functions.keys().collect();
+    names.extend(current.functions.keys());
+    names.sort();
+    names.dedup();
+
+    let mut changes: Vec<_> = names
+        .into_iter()
+        .map(|name| (name.clone(), size(current, name) - size(base, name)))
+        .filter(|(_, change)| *change != 0)
+        .collect();
+    changes.sort_by(|(a, a_change), (b, b_change)| {
+        b_change.abs().cmp(&a_change.abs()).then(a.cmp(b))
+    });
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(functions: &[(&str, u64)]) -> Size {
+        let functions: BTreeMap<_, _> = functions
+            .iter()
+            .map(|(name, size)| (name.to_string(), *size))
+            .collect();
+        Size {
+            total: 100 + functions.values().sum::<u64>(),
+            functions,
+            ..Size::default()
+        }
+    }
+
+    #[test]
+    fn diff_works() {
+        let base: Sizes = vec![
+            (
+                "erc20".to_owned(),
+                size(&[("transfer", 100), ("approve", 50)]),
+            ),
+            ("owner".to_owned(), size(&[("get_owner", 50)])),
+        ]
+        .into_iter()
+        .collect();
+        let current: Sizes = vec![
+            (
+                "erc20".to_owned(),
+                size(&[("transfer", 130), ("allowance", 40)]),
+            ),
+            ("empty".to_owned(), size(&[])),
+        ]
+        .into_iter()
+        .collect();
+
+        let changes = diff(&base, &current);
+        assert_eq!(
+            changes,
+            [
+                Change {
+                    contract: "empty".to_owned(),
+                    base: None,
+                    current: 100,
+                    functions: Vec::new(),
+                },
+                Change {
+                    contract: "erc20".to_owned(),
+                    base: Some(250),
+                    current: 270,
+                    functions: vec![
+                        ("approve".to_owned(), -50),
+                        ("allowance".to_owned(), 40),
+                        ("transfer".to_owned(), 30),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(changes[0].growth(), None);
+        assert_eq!(changes[1].growth(), Some(8.0));
+        assert!(!changes[0].exceeds(0.0));
+        assert!(changes[1].exceeds(5.0));
+        assert!(!changes[1].exceeds(8.0));
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let sizes: Sizes = vec![("erc20".to_owned(), size(&[("transfer", 100)]))]
+            .into_iter()
+            .collect();
+        let path = std::env::temp_dir()
+            .join(format!("code-size-baseline-{}.json", std::process::id()));
+        save(&path, &sizes).expect("Cannot save the baseline");
+        let loaded = load(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded.expect("Cannot load the baseline"), sizes);
+    }
+}
@@ -0,0 +1,72 @@
+//! Measures the Wasm binaries of the contracts by function, to track how much code
+//! the modular `Erc20Impl` and the `contract::traits` bounds generate compared to
+//! monolithic contracts.
+//!
+//! The sizes are stored in a baseline file, which later builds are diffed against.
+
+mod baseline;
+mod report;
+mod size;
+
+pub use baseline::{
+    diff,
+    load,
+    save,
+    Change,
+    Sizes,
+};
+pub use report::{
+    changes,
+    comparison,
+    table,
+};
+pub use size::{
+    analyze,
+    Size,
+};
+
+use std::{
+    fmt,
+    io,
+};
+
+/// The errors of the size analysis.
+#[derive(Debug)]
+pub enum Error {
+    /// Returned if the baseline file cannot be read or written.
+    Io(io::Error),
+    /// Returned if the baseline file is not valid JSON of the sizes.
+    Json(serde_json::Error),
+    /// Returned if a binary is not a valid Wasm module.
+    Wasm(wasmparser::BinaryReaderError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Json(error) => write!(f, "invalid baseline: {}", error),
+            Self::Wasm(error) => write!(f, "invalid Wasm binary: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<wasmparser::BinaryReaderError> for Error {
+    fn from(error: wasmparser::BinaryReaderError) -> Self {
+        Self::Wasm(error)
+    }
+}
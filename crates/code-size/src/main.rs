@@ -0,0 +1,135 @@
+//! Builds the contracts and prints the size of their binaries.
+//!
+//! ```text
+//! cargo run -p code-size -- [--contract <name>]... [--wasm <name>=<path>]...
+//!     [--baseline <path>] [--threshold <percent>] [--functions <count>]
+//!     [--save <path>]
+//! ```
+//!
+//! `--contract` picks the contracts to measure, all of them by default. `--wasm`
+//! takes the binary of a contract from a `.wasm` file or a `.contract` bundle
+//! instead of building it. With `--baseline` the sizes are diffed against the ones
+//! stored in `<path>`, listing the `--functions` largest changes of each contract,
+//! and the tool fails if a contract grew by more than `--threshold` percent, 5% by
+//! default. `--save` stores the sizes as the new baseline, unless the check failed.
+
+use code_size::{
+    analyze,
+    changes,
+    comparison,
+    diff,
+    load,
+    save,
+    table,
+    Sizes,
+};
+use contract_build::{
+    Args,
+    CONTRACTS,
+    MODULAR,
+    MONOLITHIC,
+};
+use std::{
+    error::Error,
+    path::PathBuf,
+    process,
+};
+
+const USAGE: &str = "usage: code-size [--contract <name>]... [--wasm <name>=<path>]... \
+                     [--baseline <path>] [--threshold <percent>] [--functions <count>] \
+                     [--save <path>]";
+
+/// The options of the tool besides the shared `Args`.
+struct Options {
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    functions: usize,
+    save: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<(Args, Options), String> {
+    let mut options = Options {
+        baseline: None,
+        threshold: 5.0,
+        functions: 10,
+        save: None,
+    };
+    let args = Args::parse(USAGE, |option, value| {
+        match option {
+            "--baseline" => options.baseline = Some(value.into()),
+            "--threshold" => {
+                options.threshold = value.parse().map_err(|_| {
+                    format!("--threshold takes a percentage, not {}", value)
+                })?
+            }
+            "--functions" => {
+                options.functions = value
+                    .parse()
+                    .map_err(|_| format!("--functions takes a count, not {}", value))?
+            }
+            "--save" => options.save = Some(value.into()),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok((args, options))
+}
+
+fn main() {
+    let (args, options) = parse_args().unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(2)
+    });
+    match code_size(&args, &options) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(1)
+        }
+    }
+}
+
+/// Measures the contracts, returns whether none grew beyond the threshold.
+fn code_size(args: &Args, options: &Options) -> Result<bool, Box<dyn Error>> {
+    let mut sizes = Sizes::new();
+    for contract in CONTRACTS {
+        if !args.picks(contract.name) {
+            continue
+        }
+        let size = analyze(&args.binary(contract.name)?)?;
+        sizes.insert(contract.name.to_owned(), size);
+    }
+
+    println!("{}", table(&sizes));
+    if sizes.contains_key(MODULAR) && sizes.contains_key(MONOLITHIC) {
+        println!("{}", comparison(&sizes, MODULAR, MONOLITHIC));
+    }
+
+    let mut passed = true;
+    if let Some(baseline) = &options.baseline {
+        let changes_since = diff(&load(baseline)?, &sizes);
+        println!("{}", changes(&changes_since, options.functions));
+        for change in changes_since
+            .iter()
+            .filter(|c| c.exceeds(options.threshold))
+        {
+            eprintln!(
+                "{} grew by {:.1}%, more than {}%",
+                change.contract,
+                change.growth().unwrap_or_default(),
+                options.threshold
+            );
+            passed = false;
+        }
+    }
+    if let Some(path) = &options.save {
+        // A growth beyond the threshold is not taken as the new baseline.
+        if passed {
+            save(path, &sizes)?;
+        } else {
+            eprintln!("{} is not saved, the check failed", path.display());
+        }
+    }
+    Ok(passed)
+}
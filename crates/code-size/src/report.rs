@@ -0,0 +1,196 @@
+//! Tables of the sizes, in Markdown.
+
+use crate::{
+    Change,
+    Sizes,
+};
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    fmt::Write,
+};
+
+/// Returns a table of the size of every contract of `sizes`.
+pub fn table(sizes: &Sizes) -> String {
+    let mut table = String::from(
+        "| contract | total | code | data | functions |\n|---|--:|--:|--:|--:|\n",
+    );
+    for (contract, size) in sizes {
+        writeln!(
+            table,
+            "| {} | {} | {} | {} | {} |",
+            contract,
+            size.total,
+            size.code,
+            size.data,
+            size.functions.len()
+        )
+        .expect("Cannot write to a string");
+    }
+    table
+}
+
+/// Returns a table comparing the code of each crate in `base` and `other`, with
+/// the overhead of `base` relative to `other`.
+pub fn comparison(sizes: &Sizes, base: &str, other: &str) -> String {
+    let mut table = format!(
+        "| crate | {} | {} | overhead |\n|---|--:|--:|--:|\n",
+        base, other
+    );
+    let (base_crates, other_crates) = match (sizes.get(base), sizes.get(other)) {
+        (Some(base), Some(other)) => (base.crates(), other.crates()),
+        _ => return table,
+    };
+    let crates: BTreeSet<_> = base_crates.keys().chain(other_crates.keys()).collect();
+    let mut rows: Vec<_> = crates
+        .into_iter()
+        .map(|&name| {
+            let size =
+                |crates: &BTreeMap<&str, u64>| crates.get(name).copied().unwrap_or(0);
+            (name, size(&base_crates), size(&other_crates))
+        })
+        .collect();
+    rows.push(("total", sizes[base].total, sizes[other].total));
+    for (name, base_size, other_size) in rows {
+        let overhead =
+            (base_size as f64 - other_size as f64) / other_size.max(1) as f64 * 100.0;
+        writeln!(
+            table,
+            "| {} | {} | {} | {:+.1}% |",
+            name, base_size, other_size, overhead
+        )
+        .expect("Cannot write to a string");
+    }
+    table
+}
+
+/// Returns a table of `changes`, with the `functions` largest changes of the
+/// functions of each contract.
+pub fn changes(changes: &[Change], functions: usize) -> String {
+    let mut table =
+        String::from("| contract | baseline | current | growth |\n|---|--:|--:|--:|\n");
+    for change in changes {
+        let (base, growth) = match (change.base, change.growth()) {
+            (Some(base), Some(growth)) => (base.to_string(), format!("{:+.1}%", growth)),
+            _ => ("-".to_owned(), "new".to_owned()),
+        };
+        writeln!(
+            table,
+            "| {} | {} | {} | {} |",
+            change.contract, base, change.current, growth
+        )
+        .expect("Cannot write to a string");
+    }
+    for change in changes.iter().filter(|change| !change.functions.is_empty()) {
+        write!(
+            table,
+            "\n{}:\n\n| function | change |\n|---|--:|\n",
+            change.contract
+        )
+        .expect("Cannot write to a string");
+        for (name, size) in change.functions.iter().take(functions) {
+            writeln!(table, "| `{}` | {:+} |", name, size)
+                .expect("Cannot write to a string");
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Size;
+
+    fn sizes() -> Sizes {
+        let size = |total, functions: &[(&str, u64)]| {
+            Size {
+                total,
+                code: total - 10,
+                data: 5,
+                functions: functions
+                    .iter()
+                    .map(|(name, size)| (name.to_string(), *size))
+                    .collect(),
+            }
+        };
+        vec![
+            (
+                "erc20".to_owned(),
+                size(
+                    300,
+                    &[("erc20_basic::transfer", 150), ("ink_env::call", 100)],
+                ),
+            ),
+            (
+                "erc20owner".to_owned(),
+                size(200, &[("erc20owner::transfer", 100), ("ink_env::call", 80)]),
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn table_works() {
+        assert_eq!(
+            table(&sizes()).lines().skip(2).collect::<Vec<_>>(),
+            [
+                "| erc20 | 300 | 290 | 5 | 2 |",
+                "| erc20owner | 200 | 190 | 5 | 2 |"
+            ]
+        );
+    }
+
+    #[test]
+    fn comparison_works() {
+        let comparison = comparison(&sizes(), "erc20", "erc20owner");
+        assert_eq!(
+            comparison.lines().skip(2).collect::<Vec<_>>(),
+            [
+                "| erc20_basic | 150 | 0 | +15000.0% |",
+                "| erc20owner | 0 | 100 | -100.0% |",
+                "| ink_env | 100 | 80 | +25.0% |",
+                "| total | 300 | 200 | +50.0% |",
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_works() {
+        let changes = changes(
+            &[
+                Change {
+                    contract: "empty".to_owned(),
+                    base: None,
+                    current: 100,
+                    functions: Vec::new(),
+                },
+                Change {
+                    contract: "erc20".to_owned(),
+                    base: Some(200),
+                    current: 210,
+                    functions: vec![
+                        ("transfer".to_owned(), 20),
+                        ("approve".to_owned(), -10),
+                    ],
+                },
+            ],
+            1,
+        );
+        assert_eq!(
+            changes.lines().skip(2).collect::<Vec<_>>(),
+            [
+                "| empty | - | 100 | new |",
+                "| erc20 | 200 | 210 | +5.0% |",
+                "",
+                "erc20:",
+                "",
+                "| function | change |",
+                "|---|--:|",
+                "| `transfer` | +20 |",
+            ]
+        );
+    }
+}
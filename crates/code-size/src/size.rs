@@ -0,0 +1,224 @@
+//! The size of a Wasm binary, by section and by function.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::collections::BTreeMap;
+use wasmparser::{
+    BinaryReaderError,
+    ExternalKind,
+    Name,
+    NameSectionReader,
+    Parser,
+    Payload,
+    TypeRef,
+};
+
+/// The size of a contract, in bytes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Size {
+    /// The size of the whole binary.
+    pub total: u64,
+    /// The size of the code section.
+    pub code: u64,
+    /// The size of the data section.
+    pub data: u64,
+    /// The size of the body of each function, by name.
+    pub functions: BTreeMap<String, u64>,
+}
+
+impl Size {
+    /// Returns the size of the functions of each crate, the first segment of their
+    /// path.
+    pub fn crates(&self) -> BTreeMap<&str, u64> {
+        let mut crates = BTreeMap::new();
+        for (name, size) in &self.functions {
+            *crates.entry(crate_of(name)).or_default() += size;
+        }
+        crates
+    }
+}
+
+/// Measures a Wasm binary.
+///
+/// The functions are named by the `name` section, without the hash rustc appends to
+/// the symbols so that the names are stable across builds. A binary without names,
+/// e.g. one optimized by `wasm-opt`, has its functions named by their exports or
+/// as `function[<index>]`.
+pub fn analyze(wasm: &[u8]) -> Result<Size, BinaryReaderError> {
+    let mut size = Size {
+        total: wasm.len() as u64,
+        ..Size::default()
+    };
+    let mut imported = 0;
+    let mut bodies = Vec::new();
+    let mut names = BTreeMap::new();
+    let mut exports = BTreeMap::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let TypeRef::Func(_) = import?.ty {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if export.kind == ExternalKind::Func {
+                        exports.insert(export.index, export.name.to_owned());
+                    }
+                }
+            }
+            Payload::CodeSectionStart { range, .. } => {
+                size.code = range.len() as u64;
+            }
+            Payload::CodeSectionEntry(body) => bodies.push(body.range().len() as u64),
+            Payload::DataSection(reader) => size.data = reader.range().len() as u64,
+            Payload::CustomSection(reader) if reader.name() == "name" => {
+                let names_reader =
+                    NameSectionReader::new(reader.data(), reader.data_offset());
+                for subsection in names_reader {
+                    if let Name::Function(map) = subsection? {
+                        for naming in map {
+                            let naming = naming?;
+                            let name = strip_hash(naming.name).to_owned();
+                            names.insert(naming.index, name);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (i, body) in bodies.into_iter().enumerate() {
+        let index = imported + i as u32;
+        let name = names
+            .remove(&index)
+            .or_else(|| exports.remove(&index))
+            .unwrap_or_else(|| format!("function[{}]", index));
+        // Monomorphized copies of a generic function share their demangled name.
+        *size.functions.entry(name).or_default() += body;
+    }
+    Ok(size)
+}
+
+/// Strips the hash of a demangled symbol, e.g. `::h0123456789abcdef`.
+fn strip_hash(name: &str) -> &str {
+    match name.rsplit_once("::h") {
+        Some((path, hash))
+            if hash.len() == 16 && hash.bytes().all(|b| b.is_ascii_hexdigit()) =>
+        {
+            path
+        }
+        _ => name,
+    }
+}
+
+/// Returns the crate of a function, `<T as Trait>::f` belongs to the crate of `T`.
+fn crate_of(name: &str) -> &str {
+    let path = name.trim_start_matches('<').trim_start_matches('&');
+    let path = path.trim_start_matches("mut ").trim_start_matches("dyn ");
+    match path.find(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        Some(end) if path[end..].starts_with("::") => &path[..end],
+        _ => "<unknown>",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"
+        (module
+            (import "seal0" "seal_input" (func $input (param i32 i32)))
+            (func $erc20_basic::transfer::h0123456789abcdef (result i32)
+                (i32.add (i32.const 1) (i32.const 2)))
+            (func $core::panicking::panic::h00000000000000ff
+                unreachable)
+            (func)
+            (func (export "call") (result i32)
+                (call $erc20_basic::transfer::h0123456789abcdef))
+            (memory 1)
+            (data (i32.const 0) "ink!"))
+    "#;
+
+    #[test]
+    fn analyze_works() {
+        let wasm = wat::parse_str(MODULE).expect("Cannot parse the module");
+        let size = analyze(&wasm).expect("Cannot analyze the module");
+        assert_eq!(size.total, wasm.len() as u64);
+        assert!(size.data > 4);
+
+        let names: Vec<_> = size.functions.keys().map(String::as_str).collect();
+        assert_eq!(
+            names,
+            [
+                "call",
+                "core::panicking::panic",
+                "erc20_basic::transfer",
+                "function[3]",
+            ]
+        );
+        // Each body is its locals and its instructions.
+        assert_eq!(size.functions["erc20_basic::transfer"], 1 + 2 + 2 + 1 + 1);
+        assert_eq!(size.functions["core::panicking::panic"], 1 + 1 + 1);
+        assert_eq!(size.functions["function[3]"], 1 + 1);
+        // The section is the count of bodies, and each body with its size.
+        let bodies: u64 = size.functions.values().sum();
+        assert_eq!(size.code, 1 + bodies + size.functions.len() as u64);
+
+        let crates: Vec<_> = size.crates().into_iter().collect();
+        let unknown = size.functions["call"] + size.functions["function[3]"];
+        assert_eq!(
+            crates,
+            [("<unknown>", unknown), ("core", 3), ("erc20_basic", 7)]
+        );
+    }
+
+    #[test]
+    fn analyze_works_without_names() {
+        let bundle = contract_build::contracts_dir()
+            .join("cross-calling/call-by-dep/artifacts/erc20.contract");
+        let wasm = contract_build::read_wasm(&bundle).expect("Cannot read the bundle");
+        let size = analyze(&wasm).expect("Cannot analyze the bundle");
+        assert_eq!(size.total, 33182);
+        assert_eq!(size.code, 32451);
+        assert_eq!(size.functions.len(), 117);
+        assert!(size.functions.contains_key("deploy"));
+        assert!(size.functions.contains_key("call"));
+        assert!(size
+            .functions
+            .keys()
+            .any(|name| name.starts_with("function[")));
+    }
+
+    #[test]
+    fn crate_of_works() {
+        assert_eq!(crate_of("erc20_basic::Erc20Impl::transfer"), "erc20_basic");
+        assert_eq!(
+            crate_of("<erc20_basic::Erc20 as contract::Env>::env"),
+            "erc20_basic"
+        );
+        assert_eq!(
+            crate_of("<&mut T as core::fmt::Write>::write_str"),
+            "<unknown>"
+        );
+        assert_eq!(
+            crate_of("<&mut ink_env::Buffer as scale::Output>::write"),
+            "ink_env"
+        );
+        assert_eq!(crate_of("call"), "<unknown>");
+    }
+
+    #[test]
+    fn strip_hash_works() {
+        assert_eq!(strip_hash("a::b::h0123456789abcdef"), "a::b");
+        assert_eq!(strip_hash("a::hash"), "a::hash");
+        assert_eq!(strip_hash("a::h0123456789abcdeg"), "a::h0123456789abcdeg");
+        assert_eq!(strip_hash("deploy"), "deploy");
+    }
+}
@@ -0,0 +1,345 @@
+//! Builds the ink! contracts of `contracts/` to Wasm, for the tools which run or
+//! measure their binaries.
+//!
+//! The contracts are built the way `cargo contract build` builds them, without the
+//! optimization by `wasm-opt` afterwards: for `wasm32-unknown-unknown`, without the
+//! `std` feature and importing their memory from the host.
+
+use std::{
+    env,
+    fmt,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::Command,
+};
+
+/// The target the contracts are built for.
+pub const TARGET: &str = "wasm32-unknown-unknown";
+
+/// The flags of `cargo contract build` for the linker.
+const RUSTFLAGS: &str =
+    "-C link-arg=-zstack-size=65536 -C link-arg=--import-memory -C target-cpu=mvp";
+
+/// The modular and the monolithic ERC-20 tokens, which the tools compare.
+pub const MODULAR: &str = "erc20";
+pub const MONOLITHIC: &str = "erc20owner";
+
+/// An ink! contract of the repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contract {
+    /// The name of the crate of the contract, also the name of its binary.
+    pub name: &'static str,
+    /// The directory of the crate, relative to `contracts/`.
+    pub dir: &'static str,
+}
+
+/// All the contracts of the repository.
+pub const CONTRACTS: &[Contract] = &[
+    Contract {
+        name: "erc20",
+        dir: "redspot-erc20/contracts",
+    },
+    Contract {
+        name: "erc721",
+        dir: "redspot-erc20/contracts/erc721",
+    },
+    Contract {
+        name: "erc1155",
+        dir: "redspot-erc20/contracts/erc1155",
+    },
+    Contract {
+        name: "dividend_token",
+        dir: "redspot-erc20/contracts/dividend_token",
+    },
+    Contract {
+        name: "token_factory",
+        dir: "redspot-erc20/contracts/token_factory",
+    },
+    Contract {
+        name: "receiver_mock",
+        dir: "redspot-erc20/contracts/receiver_mock",
+    },
+    Contract {
+        name: "erc20owner",
+        dir: "ownertest/contracts/erc20owner",
+    },
+    Contract {
+        name: "owner",
+        dir: "ownertest/contracts/owner",
+    },
+    Contract {
+        name: "owner_test",
+        dir: "ownertest/contracts/ownertest",
+    },
+    Contract {
+        name: "erc20swap",
+        dir: "cross-calling/call-by-dep/contracts",
+    },
+    Contract {
+        name: "empty",
+        dir: "empty",
+    },
+];
+
+impl Contract {
+    /// Returns the contract named `name`.
+    pub fn find(name: &str) -> Result<&'static Contract, Error> {
+        CONTRACTS
+            .iter()
+            .find(|contract| contract.name == name)
+            .ok_or_else(|| Error::UnknownContract(name.to_owned()))
+    }
+
+    /// Returns the manifest of the crate of the contract.
+    pub fn manifest_path(&self) -> PathBuf {
+        contracts_dir().join(self.dir).join("Cargo.toml")
+    }
+}
+
+/// The errors of the builds.
+#[derive(Debug)]
+pub enum Error {
+    /// Returned if cargo could not be run or a binary could not be read.
+    Io(io::Error),
+    /// Returned if cargo failed to build the contract.
+    BuildFailed(&'static str),
+    /// Returned if no contract has the given name.
+    UnknownContract(String),
+    /// Returned if a `.contract` bundle holds no Wasm binary.
+    InvalidBundle(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::BuildFailed(name) => write!(f, "cannot build the contract {}", name),
+            Self::UnknownContract(name) => write!(f, "no contract is named {}", name),
+            Self::InvalidBundle(path) => {
+                write!(f, "{} holds no Wasm binary", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// The options of the command line shared by the tools.
+///
+/// `--contract` picks the contracts, all of them by default. `--wasm` takes the
+/// binary of a contract from a `.wasm` file or a `.contract` bundle instead of
+/// building it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    pub contracts: Vec<String>,
+    pub wasm: Vec<(String, PathBuf)>,
+}
+
+impl Args {
+    /// Parses the command line of the tool, see `parse_from`.
+    pub fn parse<F>(usage: &str, option: F) -> Result<Self, String>
+    where
+        F: FnMut(&str, String) -> Result<bool, String>,
+    {
+        Self::parse_from(env::args().skip(1), usage, option)
+    }
+
+    /// Parses `args`, options followed by their value. `option` takes the options of
+    /// the tool and returns whether it knows the option, the error is `usage` if no
+    /// one does.
+    pub fn parse_from<I, F>(args: I, usage: &str, mut option: F) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+        F: FnMut(&str, String) -> Result<bool, String>,
+    {
+        let mut parsed = Self::default();
+        let mut iter = args.into_iter();
+        while let Some(arg) = iter.next() {
+            let value = iter.next().ok_or_else(|| usage.to_owned())?;
+            match arg.as_str() {
+                "--contract" => parsed.contracts.push(value),
+                "--wasm" => {
+                    let (name, path) = value.split_once('=').ok_or_else(|| {
+                        format!("--wasm takes <name>=<path>, not {}", value)
+                    })?;
+                    parsed.wasm.push((name.to_owned(), path.into()));
+                }
+                _ => {
+                    if !option(&arg, value)? {
+                        return Err(usage.to_owned())
+                    }
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Returns whether the contract `name` is picked.
+    pub fn picks(&self, name: &str) -> bool {
+        self.contracts.is_empty() || self.contracts.iter().any(|picked| picked == name)
+    }
+
+    /// Returns the binary of the contract `name`, given by `--wasm` or built.
+    pub fn binary(&self, name: &str) -> Result<Vec<u8>, Error> {
+        let path = match self.wasm.iter().find(|(contract, _)| contract == name) {
+            Some((_, path)) => path.clone(),
+            None => build(Contract::find(name)?)?,
+        };
+        read_wasm(&path)
+    }
+}
+
+/// Returns the `contracts/` directory of the repository.
+pub fn contracts_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../contracts")
+}
+
+/// Returns the directory the contracts are built in, `target/contracts` of the
+/// repository, shared by the contracts so that ink! is built once.
+pub fn target_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/contracts")
+}
+
+/// Builds `contract` in release mode and returns the path of its binary.
+///
+/// The toolchain is the one of the `cargo` running the tool, `RUSTUP_TOOLCHAIN`
+/// picks another one.
+pub fn build(contract: &Contract) -> Result<PathBuf, Error> {
+    let target_dir = target_dir();
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let status = Command::new(cargo)
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(contract.manifest_path())
+        .args([
+            "--lib",
+            "--release",
+            "--no-default-features",
+            "--target",
+            TARGET,
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("RUSTFLAGS", RUSTFLAGS)
+        .status()?;
+    if !status.success() {
+        return Err(Error::BuildFailed(contract.name))
+    }
+    Ok(target_dir
+        .join(TARGET)
+        .join("release")
+        .join(contract.name)
+        .with_extension("wasm"))
+}
+
+/// Reads a Wasm binary from a `.wasm` file or from the `source.wasm` field of a
+/// `.contract` bundle of `cargo contract`.
+pub fn read_wasm(path: &Path) -> Result<Vec<u8>, Error> {
+    let bytes = fs::read(path)?;
+    if path
+        .extension()
+        .is_none_or(|extension| extension != "contract")
+    {
+        return Ok(bytes)
+    }
+    let invalid = || Error::InvalidBundle(path.to_owned());
+    let bundle: serde_json::Value =
+        serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    let wasm = bundle["source"]["wasm"].as_str().ok_or_else(invalid)?;
+    decode_hex(wasm.trim_start_matches("0x")).ok_or_else(invalid)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contracts_are_in_the_repository() {
+        for contract in CONTRACTS {
+            let manifest = fs::read_to_string(contract.manifest_path())
+                .unwrap_or_else(|_| panic!("{} has no manifest", contract.name));
+            assert!(
+                manifest.contains(&format!("name = \"{}\"", contract.name)),
+                "the crate of {} has another name",
+                contract.name
+            );
+        }
+        let erc20 = Contract::find("erc20").expect("Cannot find erc20");
+        assert_eq!(erc20.dir, "redspot-erc20/contracts");
+        assert!(matches!(
+            Contract::find("erc20_basic"),
+            Err(Error::UnknownContract(_))
+        ));
+    }
+
+    #[test]
+    fn args_parse() {
+        let args = |args: &[&str]| {
+            let mut threshold = None;
+            let parsed = Args::parse_from(
+                args.iter().map(|arg| arg.to_string()),
+                "usage",
+                |option, value| {
+                    match option {
+                        "--threshold" => threshold = Some(value),
+                        _ => return Ok(false),
+                    }
+                    Ok(true)
+                },
+            );
+            parsed.map(|parsed| (parsed, threshold))
+        };
+
+        let (parsed, threshold) = args(&[
+            "--contract",
+            "erc20",
+            "--threshold",
+            "5",
+            "--wasm",
+            "owner=owner.wasm",
+        ])
+        .expect("Cannot parse the arguments");
+        assert_eq!(parsed.contracts, ["erc20"]);
+        assert_eq!(parsed.wasm, [("owner".to_owned(), "owner.wasm".into())]);
+        assert_eq!(threshold.as_deref(), Some("5"));
+        assert!(parsed.picks("erc20"));
+        assert!(!parsed.picks("owner"));
+        assert!(Args::default().picks("owner"));
+
+        assert_eq!(args(&["--contract"]), Err("usage".to_owned()));
+        assert_eq!(args(&["--unknown", "value"]), Err("usage".to_owned()));
+        assert!(args(&["--wasm", "owner.wasm"]).is_err());
+    }
+
+    #[test]
+    fn read_wasm_works() {
+        let bundle =
+            contracts_dir().join("cross-calling/call-by-dep/artifacts/erc20.contract");
+        let wasm = read_wasm(&bundle).expect("Cannot read the bundle");
+        assert_eq!(wasm[..4], *b"\0asm");
+
+        assert_eq!(decode_hex("00ff10"), Some(vec![0x00, 0xff, 0x10]));
+        assert_eq!(decode_hex("0"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+}
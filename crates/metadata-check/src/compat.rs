@@ -0,0 +1,594 @@
+//! The changes of the metadata which break the callers of a deployed contract, or
+//! the data it stored, and the compatible ones worth a note.
+
+use crate::{
+    Entry,
+    Event,
+    Function,
+    Metadata,
+};
+use std::fmt;
+
+/// A breaking change of the metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breaking {
+    /// A constructor or a message was removed.
+    Removed { function: String, selector: String },
+    /// A constructor or a message has another selector.
+    SelectorChanged {
+        function: String,
+        old: String,
+        new: String,
+    },
+    /// The types of the arguments of a constructor or a message changed.
+    ArgumentsChanged {
+        function: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    /// A message returns another type.
+    ReturnTypeChanged {
+        function: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A payable constructor or message is not payable anymore.
+    NotPayable { function: String },
+    /// The contract has another name, which the topics of its events hash.
+    ContractRenamed { old: String, new: String },
+    /// An event was removed.
+    EventRemoved { event: String },
+    /// An event has another index, the first byte of its encoding.
+    EventMoved {
+        event: String,
+        old: usize,
+        new: usize,
+    },
+    /// The types of the arguments of an event changed.
+    EventArgumentsChanged {
+        event: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    /// Other arguments of an event are its topics.
+    TopicsChanged {
+        event: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    /// A field of the storage was removed.
+    StorageRemoved { path: String },
+    /// A field of the storage is stored under another key.
+    StorageMoved {
+        path: String,
+        old: String,
+        new: String,
+    },
+    /// A field of the storage has another type.
+    StorageTypeChanged {
+        path: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for Breaking {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Removed { function, selector } => {
+                write!(f, "{} ({}) was removed", function, selector)
+            }
+            Self::SelectorChanged { function, old, new } => {
+                write!(
+                    f,
+                    "the selector of {} changed from {} to {}",
+                    function, old, new
+                )
+            }
+            Self::ArgumentsChanged { function, old, new } => {
+                write!(
+                    f,
+                    "the arguments of {} changed from ({}) to ({})",
+                    function,
+                    old.join(", "),
+                    new.join(", ")
+                )
+            }
+            Self::ReturnTypeChanged { function, old, new } => {
+                let or_nothing = |ty: &Option<String>| {
+                    ty.clone().unwrap_or_else(|| "nothing".to_owned())
+                };
+                write!(
+                    f,
+                    "{} returns {} instead of {}",
+                    function,
+                    or_nothing(new),
+                    or_nothing(old)
+                )
+            }
+            Self::NotPayable { function } => write!(f, "{} is not payable", function),
+            Self::ContractRenamed { old, new } => {
+                write!(
+                    f,
+                    "the contract was renamed from {} to {}, the topics of its events \
+                     changed",
+                    old, new
+                )
+            }
+            Self::EventRemoved { event } => write!(f, "the event {} was removed", event),
+            Self::EventMoved { event, old, new } => {
+                write!(
+                    f,
+                    "the index of the event {} changed from {} to {}",
+                    event, old, new
+                )
+            }
+            Self::EventArgumentsChanged { event, old, new } => {
+                write!(
+                    f,
+                    "the arguments of the event {} changed from ({}) to ({})",
+                    event,
+                    old.join(", "),
+                    new.join(", ")
+                )
+            }
+            Self::TopicsChanged { event, old, new } => {
+                write!(
+                    f,
+                    "the topics of the event {} changed from [{}] to [{}]",
+                    event,
+                    old.join(", "),
+                    new.join(", ")
+                )
+            }
+            Self::StorageRemoved { path } => {
+                write!(f, "the storage field {} was removed", path)
+            }
+            Self::StorageMoved { path, old, new } => {
+                write!(
+                    f,
+                    "the storage field {} moved from {} to {}",
+                    path, old, new
+                )
+            }
+            Self::StorageTypeChanged { path, old, new } => {
+                write!(
+                    f,
+                    "the storage field {} changed from {} to {}",
+                    path, old, new
+                )
+            }
+        }
+    }
+}
+
+/// A compatible change of the metadata, reported for information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Info {
+    /// A field of the storage was renamed, it keeps its key.
+    StorageRenamed { old: String, new: String },
+}
+
+impl fmt::Display for Info {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::StorageRenamed { old, new } => {
+                write!(f, "the storage field {} was renamed to {}", old, new)
+            }
+        }
+    }
+}
+
+/// The changes from the metadata of a deployed contract to the metadata of its new
+/// build.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub breaking: Vec<Breaking>,
+    pub info: Vec<Info>,
+}
+
+/// Returns the changes from `old` to `new` which break the callers of a contract
+/// deployed with `old`, or the data it stored.
+///
+/// Additions are compatible: new constructors, messages and events, new fields of
+/// the storage under keys of their own, and arguments which are not topics or
+/// fields of the storage being renamed.
+pub fn check(old: &Metadata, new: &Metadata) -> Vec<Breaking> {
+    compare(old, new).breaking
+}
+
+/// Returns the breaking changes from `old` to `new`, see `check`, and the
+/// compatible changes worth a note.
+pub fn compare(old: &Metadata, new: &Metadata) -> Report {
+    let mut report = Report::default();
+    functions(&old.constructors, &new.constructors, &mut report.breaking);
+    functions(&old.messages, &new.messages, &mut report.breaking);
+    events(old, new, &mut report.breaking);
+    storage(&old.storage, &new.storage, &mut report);
+    report
+}
+
+/// Compares the functions by selector, which callers use, and by name to tell a
+/// removed function from one whose selector changed.
+fn functions(old: &[Function], new: &[Function], changes: &mut Vec<Breaking>) {
+    for function in old {
+        let (same, by_name) = match new.iter().find(|f| f.selector == function.selector) {
+            Some(same) => (same, false),
+            None => {
+                match new.iter().find(|f| f.name == function.name) {
+                    Some(same) => (same, true),
+                    None => {
+                        changes.push(Breaking::Removed {
+                            function: function.name.clone(),
+                            selector: function.selector.clone(),
+                        });
+                        continue
+                    }
+                }
+            }
+        };
+        if by_name {
+            changes.push(Breaking::SelectorChanged {
+                function: function.name.clone(),
+                old: function.selector.clone(),
+                new: same.selector.clone(),
+            });
+        }
+
+        let types = |function: &Function| -> Vec<String> {
+            function.args.iter().map(|(_, ty)| ty.clone()).collect()
+        };
+        if types(function) != types(same) {
+            changes.push(Breaking::ArgumentsChanged {
+                function: function.name.clone(),
+                old: types(function),
+                new: types(same),
+            });
+        }
+        if function.return_type != same.return_type {
+            changes.push(Breaking::ReturnTypeChanged {
+                function: function.name.clone(),
+                old: function.return_type.clone(),
+                new: same.return_type.clone(),
+            });
+        }
+        if function.payable && !same.payable {
+            changes.push(Breaking::NotPayable {
+                function: function.name.clone(),
+            });
+        }
+    }
+}
+
+/// Compares the events by name, their index and their arguments are encoded in
+/// what the contract emits.
+///
+/// The first topic of an event hashes the names of the contract and of the event.
+/// ink! hashes the name of the storage struct, the metadata only has the name of
+/// the crate, which is compared instead.
+fn events(old: &Metadata, new: &Metadata, changes: &mut Vec<Breaking>) {
+    if old.name != new.name && !old.events.is_empty() {
+        changes.push(Breaking::ContractRenamed {
+            old: old.name.clone(),
+            new: new.name.clone(),
+        });
+    }
+    let (old, new) = (&old.events, &new.events);
+    for (index, event) in old.iter().enumerate() {
+        let same = new.iter().enumerate().find(|(_, e)| e.name == event.name);
+        let (new_index, same) = match same {
+            Some(same) => same,
+            None => {
+                changes.push(Breaking::EventRemoved {
+                    event: event.name.clone(),
+                });
+                continue
+            }
+        };
+        if index != new_index {
+            changes.push(Breaking::EventMoved {
+                event: event.name.clone(),
+                old: index,
+                new: new_index,
+            });
+        }
+
+        let types = |event: &Event| -> Vec<String> {
+            event.args.iter().map(|arg| arg.ty.clone()).collect()
+        };
+        if types(event) != types(same) {
+            changes.push(Breaking::EventArgumentsChanged {
+                event: event.name.clone(),
+                old: types(event),
+                new: types(same),
+            });
+        }
+        let topics = |event: &Event| -> Vec<String> {
+            event
+                .args
+                .iter()
+                .filter(|arg| arg.indexed)
+                .map(|arg| arg.name.clone())
+                .collect()
+        };
+        if topics(event) != topics(same) {
+            changes.push(Breaking::TopicsChanged {
+                event: event.name.clone(),
+                old: topics(event),
+                new: topics(same),
+            });
+        }
+    }
+}
+
+/// Compares the storage by the key of the fields, which the stored data is under,
+/// preferring the field of the same type and then of the same path where several
+/// share the key, e.g. the variants of an enum. A field with no entry under its key
+/// is compared by path to tell a moved field from a removed one.
+fn storage(old: &[Entry], new: &[Entry], report: &mut Report) {
+    for entry in old {
+        let same_key = new
+            .iter()
+            .filter(|e| e.key == entry.key)
+            .max_by_key(|e| (e.ty == entry.ty, e.path == entry.path));
+        let same = match same_key {
+            Some(same) => {
+                if same.path != entry.path {
+                    report.info.push(Info::StorageRenamed {
+                        old: entry.path.clone(),
+                        new: same.path.clone(),
+                    });
+                }
+                same
+            }
+            None => {
+                match new.iter().find(|e| e.path == entry.path) {
+                    Some(same) => {
+                        report.breaking.push(Breaking::StorageMoved {
+                            path: entry.path.clone(),
+                            old: entry.key.clone(),
+                            new: same.key.clone(),
+                        });
+                        same
+                    }
+                    None => {
+                        report.breaking.push(Breaking::StorageRemoved {
+                            path: entry.path.clone(),
+                        });
+                        continue
+                    }
+                }
+            }
+        };
+        if let (Some(old), Some(new)) = (&entry.ty, &same.ty) {
+            if old != new {
+                report.breaking.push(Breaking::StorageTypeChanged {
+                    path: entry.path.clone(),
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::erc20_json;
+    use serde_json::{
+        json,
+        Value,
+    };
+
+    fn erc20() -> Value {
+        serde_json::from_slice(&erc20_json()).expect("Cannot parse the metadata")
+    }
+
+    /// Returns the changes from the ERC-20 example to its metadata changed by
+    /// `change`.
+    fn compare_changed(change: impl FnOnce(&mut Value)) -> Report {
+        let old = erc20();
+        let mut new = old.clone();
+        change(&mut new);
+        let parse = |value: &Value| {
+            Metadata::parse(value.to_string().as_bytes()).expect("Cannot parse")
+        };
+        compare(&parse(&old), &parse(&new))
+    }
+
+    /// Returns the breaking changes from the ERC-20 example to its metadata changed
+    /// by `change`.
+    fn check_changed(change: impl FnOnce(&mut Value)) -> Vec<Breaking> {
+        compare_changed(change).breaking
+    }
+
+    fn message<'a>(metadata: &'a mut Value, name: &str) -> &'a mut Value {
+        metadata["spec"]["messages"]
+            .as_array_mut()
+            .expect("no messages")
+            .iter_mut()
+            .find(|message| message["name"][0] == name)
+            .expect("no such message")
+    }
+
+    #[test]
+    fn same_metadata_is_compatible() {
+        assert_eq!(check_changed(|_| {}), []);
+    }
+
+    #[test]
+    fn additions_are_compatible() {
+        let changes = check_changed(|metadata| {
+            let mut holders = message(metadata, "total_supply").clone();
+            holders["name"] = json!(["holders"]);
+            holders["selector"] = json!("0x01020304");
+            metadata["spec"]["messages"]
+                .as_array_mut()
+                .expect("no messages")
+                .push(holders);
+            message(metadata, "transfer")["args"][0]["name"] = json!("recipient");
+            message(metadata, "approve")["payable"] = json!(true);
+        });
+        assert_eq!(changes, []);
+    }
+
+    #[test]
+    fn changed_messages_are_breaking() {
+        let changes = check_changed(|metadata| {
+            metadata["spec"]["messages"]
+                .as_array_mut()
+                .expect("no messages")
+                .retain(|message| message["name"][0] != "allowance");
+            message(metadata, "transfer")["selector"] = json!("0x01020304");
+            // `u128` instead of `AccountId`.
+            message(metadata, "balance_of")["args"][0]["type"]["type"] = json!(1);
+            message(metadata, "total_supply")["returnType"] = Value::Null;
+        });
+        assert_eq!(
+            changes,
+            [
+                Breaking::ReturnTypeChanged {
+                    function: "total_supply".to_owned(),
+                    old: Some("u128".to_owned()),
+                    new: None,
+                },
+                Breaking::ArgumentsChanged {
+                    function: "balance_of".to_owned(),
+                    old: vec!["ink_env::types::AccountId([u8; 32])".to_owned()],
+                    new: vec!["u128".to_owned()],
+                },
+                Breaking::Removed {
+                    function: "allowance".to_owned(),
+                    selector: "0x6a00165e".to_owned(),
+                },
+                Breaking::SelectorChanged {
+                    function: "transfer".to_owned(),
+                    old: "0x84a15da1".to_owned(),
+                    new: "0x01020304".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            changes[3].to_string(),
+            "the selector of transfer changed from 0x84a15da1 to 0x01020304"
+        );
+    }
+
+    #[test]
+    fn changed_events_are_breaking() {
+        let changes = check_changed(|metadata| {
+            let events = metadata["spec"]["events"]
+                .as_array_mut()
+                .expect("no events");
+            events.swap(0, 1);
+            // `Approval` is first now.
+            events[1]["args"][2]["indexed"] = json!(false);
+            events[0]["args"][2]["type"]["type"] = json!(3);
+        });
+        assert_eq!(
+            changes,
+            [
+                Breaking::EventMoved {
+                    event: "Transfer".to_owned(),
+                    old: 0,
+                    new: 1,
+                },
+                Breaking::TopicsChanged {
+                    event: "Transfer".to_owned(),
+                    old: vec!["from".to_owned(), "to".to_owned(), "value".to_owned()],
+                    new: vec!["from".to_owned(), "to".to_owned()],
+                },
+                Breaking::EventMoved {
+                    event: "Approval".to_owned(),
+                    old: 1,
+                    new: 0,
+                },
+                Breaking::EventArgumentsChanged {
+                    event: "Approval".to_owned(),
+                    old: vec![
+                        "ink_env::types::AccountId([u8; 32])".to_owned(),
+                        "ink_env::types::AccountId([u8; 32])".to_owned(),
+                        "u128".to_owned(),
+                    ],
+                    new: vec![
+                        "ink_env::types::AccountId([u8; 32])".to_owned(),
+                        "ink_env::types::AccountId([u8; 32])".to_owned(),
+                        "u32".to_owned(),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn renamed_contract_is_breaking() {
+        let changes = check_changed(|metadata| {
+            metadata["contract"]["name"] = json!("token");
+        });
+        assert_eq!(
+            changes,
+            [Breaking::ContractRenamed {
+                old: "erc20".to_owned(),
+                new: "token".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn changed_storage_is_breaking() {
+        let key = format!("0x{:0<64}", "07");
+        let changes = check_changed(|metadata| {
+            let fields = &mut metadata["storage"]["struct"]["fields"];
+            fields[0]["layout"]["cell"]["key"] = json!(key);
+            fields[0]["layout"]["cell"]["ty"] = json!(3);
+        });
+        assert_eq!(
+            changes,
+            [
+                Breaking::StorageMoved {
+                    path: "total_supply".to_owned(),
+                    old: format!("0x{:0<64}", "00"),
+                    new: key,
+                },
+                Breaking::StorageTypeChanged {
+                    path: "total_supply".to_owned(),
+                    old: "u128".to_owned(),
+                    new: "u32".to_owned(),
+                },
+            ]
+        );
+
+        // The data stays under its key with its type.
+        let changes = check_changed(|metadata| {
+            let fields = &mut metadata["storage"]["struct"]["fields"];
+            fields[0]["layout"]["cell"]["ty"] = json!(3);
+        });
+        assert_eq!(
+            changes,
+            [Breaking::StorageTypeChanged {
+                path: "total_supply".to_owned(),
+                old: "u128".to_owned(),
+                new: "u32".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn renamed_storage_is_compatible() {
+        let report = compare_changed(|metadata| {
+            metadata["storage"]["struct"]["fields"][2]["name"] = json!("allowed");
+        });
+        assert_eq!(report.breaking, []);
+        assert!(!report.info.is_empty());
+        assert!(report.info.iter().all(|info| {
+            let Info::StorageRenamed { old, new } = info;
+            old.starts_with("allowances")
+                && *new == old.replacen("allowances", "allowed", 1)
+        }));
+        assert_eq!(
+            report.info[0].to_string(),
+            "the storage field allowances.keys.header was renamed to allowed.keys.header"
+        );
+    }
+}
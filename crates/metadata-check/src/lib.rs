@@ -0,0 +1,71 @@
+//! Checks that the ink! metadata of a new build of a contract is compatible with
+//! the metadata of the deployed one: that its callers, the indexers of its events
+//! and the data it stored keep working after an upgrade.
+
+mod compat;
+mod metadata;
+
+pub use compat::{
+    check,
+    compare,
+    Breaking,
+    Info,
+    Report,
+};
+pub use metadata::{
+    Entry,
+    Event,
+    Function,
+    Metadata,
+    Topic,
+};
+
+use std::{
+    fmt,
+    io,
+};
+
+/// The errors of the checker.
+#[derive(Debug)]
+pub enum Error {
+    /// Returned if a metadata file cannot be read.
+    Io(io::Error),
+    /// Returned if a metadata file is not valid JSON.
+    Json(serde_json::Error),
+    /// Returned if a field of the metadata is missing or invalid.
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Json(error) => write!(f, "invalid metadata: {}", error),
+            Self::Invalid(field) => write!(f, "invalid metadata: bad {}", field),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Returns the committed metadata of the ERC-20 example of ink! 3.0.0-rc3.
+    pub fn erc20_json() -> Vec<u8> {
+        let path = contract_build::contracts_dir()
+            .join("cross-calling/call-by-dep/artifacts/erc20.json");
+        std::fs::read(path).expect("Cannot read the metadata")
+    }
+}
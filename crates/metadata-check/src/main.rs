@@ -0,0 +1,62 @@
+//! Reports the breaking changes between the metadata of a deployed contract and
+//! the metadata of its new build.
+//!
+//! ```text
+//! cargo run -p metadata-check -- <deployed> <new>
+//! ```
+//!
+//! Both files are the `.json` metadata or the `.contract` bundle of
+//! `cargo contract`. The checker fails if it finds a breaking change, the compatible
+//! changes worth a note, e.g. a renamed storage field, are printed too.
+
+use metadata_check::{
+    compare,
+    Metadata,
+};
+use std::{
+    env,
+    error::Error,
+    fs,
+    process,
+};
+
+const USAGE: &str = "usage: metadata-check <deployed> <new>";
+
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("{}", USAGE);
+        process::exit(2)
+    }
+    match metadata_check(&args[0], &args[1]) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(1)
+        }
+    }
+}
+
+/// Prints the changes from `deployed` to `new`, returns whether none of them is
+/// breaking.
+fn metadata_check(deployed: &str, new: &str) -> Result<bool, Box<dyn Error>> {
+    let read = |path| -> Result<_, Box<dyn Error>> {
+        let metadata = Metadata::parse(&fs::read(path)?)
+            .map_err(|error| format!("{}: {}", path, error))?;
+        Ok(metadata)
+    };
+    let report = compare(&read(deployed)?, &read(new)?);
+    for info in &report.info {
+        println!("note: {}", info);
+    }
+    if report.breaking.is_empty() {
+        println!("{} is compatible with {}", new, deployed);
+        return Ok(true)
+    }
+    println!("{} breaks {}:", new, deployed);
+    for change in &report.breaking {
+        println!("- {}", change);
+    }
+    Ok(false)
+}
@@ -0,0 +1,428 @@
+//! The parts of the ink! metadata which the callers of a contract depend on.
+//!
+//! The ids of the type registry change from one build to the other, so the types
+//! are compared by a signature resolved from the registry instead, e.g.
+//! `ink_env::types::AccountId([u8; 32])` for an account.
+
+use crate::Error;
+use serde_json::Value;
+
+/// A constructor or a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    /// The name, with the path of its trait if any, e.g. `Erc20::transfer`.
+    pub name: String,
+    pub selector: String,
+    /// The names and the types of the arguments.
+    pub args: Vec<(String, String)>,
+    /// The returned type, `None` if the message returns nothing.
+    pub return_type: Option<String>,
+    pub payable: bool,
+}
+
+/// An argument of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Topic {
+    pub name: String,
+    pub ty: String,
+    /// Whether the argument is a topic of the event.
+    pub indexed: bool,
+}
+
+/// An event, in the order of the events of the contract which is its index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: String,
+    pub args: Vec<Topic>,
+}
+
+/// A cell, a hash map or an array of the storage layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The path of the fields to the entry, e.g. `balances.values`.
+    pub path: String,
+    /// The key of a cell, the offset of a hash map or an array.
+    pub key: String,
+    /// The type of a cell, `None` for hash maps and arrays whose cells are entries
+    /// of their own.
+    pub ty: Option<String>,
+}
+
+/// The metadata of a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// The name of the contract, the name of its crate.
+    pub name: String,
+    pub constructors: Vec<Function>,
+    pub messages: Vec<Function>,
+    pub events: Vec<Event>,
+    pub storage: Vec<Entry>,
+}
+
+impl Metadata {
+    /// Parses the metadata of `cargo contract`, from a `.json` file or a `.contract`
+    /// bundle which holds it too.
+    pub fn parse(json: &[u8]) -> Result<Self, Error> {
+        let metadata: Value = serde_json::from_slice(json)?;
+        let types = Types(array(&metadata["types"], "types")?);
+        let spec = &metadata["spec"];
+        let functions = |key| {
+            array(&spec[key], "spec")?
+                .iter()
+                .map(|function| types.function(function))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let constructors = functions("constructors")?;
+        let messages = functions("messages")?;
+        let events = array(&spec["events"], "spec")?
+            .iter()
+            .map(|event| types.event(event))
+            .collect::<Result<_, _>>()?;
+        let mut storage = Vec::new();
+        types.layout(&metadata["storage"], String::new(), &mut storage)?;
+        Ok(Self {
+            name: string(&metadata["contract"]["name"], "contract.name")?,
+            constructors,
+            messages,
+            events,
+            storage,
+        })
+    }
+}
+
+/// The type registry.
+struct Types<'a>(&'a [Value]);
+
+impl<'a> Types<'a> {
+    fn function(&self, function: &Value) -> Result<Function, Error> {
+        let args = array(&function["args"], "args")?
+            .iter()
+            .map(|arg| Ok((string(&arg["name"], "args.name")?, self.reference(arg)?)))
+            .collect::<Result<_, Error>>()?;
+        let return_type = match &function["returnType"] {
+            Value::Null => None,
+            ty => Some(self.signature(id(&ty["type"])?, &mut Vec::new())?),
+        };
+        Ok(Function {
+            name: path(&function["name"])?,
+            selector: string(&function["selector"], "selector")?,
+            args,
+            return_type,
+            payable: function["payable"].as_bool().unwrap_or(false),
+        })
+    }
+
+    fn event(&self, event: &Value) -> Result<Event, Error> {
+        let args = array(&event["args"], "args")?
+            .iter()
+            .map(|arg| {
+                Ok(Topic {
+                    name: string(&arg["name"], "args.name")?,
+                    ty: self.reference(arg)?,
+                    indexed: arg["indexed"].as_bool().unwrap_or(false),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Event {
+            name: string(&event["name"], "events.name")?,
+            args,
+        })
+    }
+
+    /// Returns the signature of the type of an argument.
+    fn reference(&self, arg: &Value) -> Result<String, Error> {
+        self.signature(id(&arg["type"]["type"])?, &mut Vec::new())
+    }
+
+    /// Collects the entries of `layout`, a field at `at` of the storage.
+    fn layout(
+        &self,
+        layout: &Value,
+        at: String,
+        entries: &mut Vec<Entry>,
+    ) -> Result<(), Error> {
+        if let Some(cell) = layout.get("cell") {
+            entries.push(Entry {
+                path: at,
+                key: string(&cell["key"], "storage.cell.key")?,
+                ty: Some(self.signature(id(&cell["ty"])?, &mut Vec::new())?),
+            });
+        } else if let Some(hash) = layout.get("hash") {
+            entries.push(Entry {
+                path: at.clone(),
+                key: string(&hash["offset"], "storage.hash.offset")?,
+                ty: None,
+            });
+            self.layout(&hash["layout"], join(&at, "[hash]"), entries)?;
+        } else if let Some(array) = layout.get("array") {
+            entries.push(Entry {
+                path: at.clone(),
+                key: string(&array["offset"], "storage.array.offset")?,
+                ty: None,
+            });
+            self.layout(&array["layout"], join(&at, "[index]"), entries)?;
+        } else if let Some(fields) = layout.get("struct") {
+            self.fields_layout(fields, &at, entries)?;
+        } else if let Some(variants) = layout.get("enum") {
+            entries.push(Entry {
+                path: at.clone(),
+                key: string(&variants["dispatchKey"], "storage.enum.dispatchKey")?,
+                ty: None,
+            });
+            let variants = variants["variants"]
+                .as_object()
+                .ok_or(Error::Invalid("storage.enum.variants"))?;
+            for (index, variant) in variants {
+                let at = join(&at, &format!("[{}]", index));
+                self.fields_layout(variant, &at, entries)?;
+            }
+        } else {
+            return Err(Error::Invalid("storage"))
+        }
+        Ok(())
+    }
+
+    /// Collects the entries of the fields of a struct or of a variant of an enum.
+    fn fields_layout(
+        &self,
+        fields: &Value,
+        at: &str,
+        entries: &mut Vec<Entry>,
+    ) -> Result<(), Error> {
+        for field in array(&fields["fields"], "storage.fields")? {
+            // The fields of tuple structs have no name.
+            let name = field["name"].as_str().unwrap_or("_");
+            self.layout(&field["layout"], join(at, name), entries)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the signature of the type `id`, `stack` holds the types being
+    /// resolved so that a recursive type is named by its path only where it recurs.
+    fn signature(&self, id: usize, stack: &mut Vec<usize>) -> Result<String, Error> {
+        let ty = id
+            .checked_sub(1)
+            .and_then(|index| self.0.get(index))
+            .ok_or(Error::Invalid("types"))?;
+        let path = path(&ty["path"]).unwrap_or_default();
+        if stack.contains(&id) {
+            return Ok(path)
+        }
+        stack.push(id);
+        let params = match ty["params"].as_array() {
+            Some(params) if !params.is_empty() => {
+                let params = params
+                    .iter()
+                    .map(|param| self.signature(self::id(param)?, stack))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("<{}>", params.join(", "))
+            }
+            _ => String::new(),
+        };
+
+        let def = &ty["def"];
+        let signature = if let Some(primitive) = def["primitive"].as_str() {
+            primitive.to_owned()
+        } else if let Some(composite) = def.get("composite") {
+            format!(
+                "{}{}{}",
+                path,
+                params,
+                self.fields(&composite["fields"], stack)?
+            )
+        } else if let Some(variant) = def.get("variant") {
+            let variants = self::array(&variant["variants"], "types.variants")?
+                .iter()
+                .map(|variant| {
+                    let name = string(&variant["name"], "types.variants.name")?;
+                    let discriminant = match variant["discriminant"].as_u64() {
+                        Some(discriminant) => format!(" = {}", discriminant),
+                        None => String::new(),
+                    };
+                    let fields = self.fields(&variant["fields"], stack)?;
+                    Ok(format!("{}{}{}", name, fields, discriminant))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            format!("{}{} {{ {} }}", path, params, variants.join(" | "))
+        } else if let Some(array) = def.get("array") {
+            let len = array["len"].as_u64().ok_or(Error::Invalid("types.array"))?;
+            format!(
+                "[{}; {}]",
+                self.signature(self::id(&array["type"])?, stack)?,
+                len
+            )
+        } else if let Some(sequence) = def.get("sequence") {
+            format!(
+                "Vec<{}>",
+                self.signature(self::id(&sequence["type"])?, stack)?
+            )
+        } else if let Some(compact) = def.get("compact") {
+            format!(
+                "Compact<{}>",
+                self.signature(self::id(&compact["type"])?, stack)?
+            )
+        } else if let Some(tuple) = def.get("tuple") {
+            let fields = self::array(tuple, "types.tuple")?
+                .iter()
+                .map(|field| self.signature(self::id(field)?, stack))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("({})", fields.join(", "))
+        } else {
+            return Err(Error::Invalid("types.def"))
+        };
+        stack.pop();
+        Ok(signature)
+    }
+
+    /// Returns the signature of the fields of a composite or of a variant, empty if
+    /// it has none.
+    fn fields(&self, fields: &Value, stack: &mut Vec<usize>) -> Result<String, Error> {
+        let fields = match fields.as_array() {
+            Some(fields) if !fields.is_empty() => fields,
+            _ => return Ok(String::new()),
+        };
+        let signatures = fields
+            .iter()
+            .map(|field| {
+                let ty = self.signature(id(&field["type"])?, stack)?;
+                Ok(match field["name"].as_str() {
+                    Some(name) => format!("{}: {}", name, ty),
+                    None => ty,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if fields[0]["name"].is_string() {
+            Ok(format!(" {{ {} }}", signatures.join(", ")))
+        } else {
+            Ok(format!("({})", signatures.join(", ")))
+        }
+    }
+}
+
+/// Returns the path of the field `name` of the storage at `at`.
+fn join(at: &str, name: &str) -> String {
+    if at.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", at, name)
+    }
+}
+
+fn array<'a>(value: &'a Value, field: &'static str) -> Result<&'a [Value], Error> {
+    value
+        .as_array()
+        .map(Vec::as_slice)
+        .ok_or(Error::Invalid(field))
+}
+
+fn string(value: &Value, field: &'static str) -> Result<String, Error> {
+    value
+        .as_str()
+        .map(str::to_owned)
+        .ok_or(Error::Invalid(field))
+}
+
+fn id(value: &Value) -> Result<usize, Error> {
+    value
+        .as_u64()
+        .map(|id| id as usize)
+        .ok_or(Error::Invalid("type"))
+}
+
+/// Joins a path of segments, e.g. the name of a message or the path of a type.
+fn path(value: &Value) -> Result<String, Error> {
+    let segments = array(value, "name")?
+        .iter()
+        .map(|segment| string(segment, "name"))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(segments.join("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let metadata = Metadata::parse(&crate::tests::erc20_json())
+            .expect("Cannot parse the metadata");
+
+        assert_eq!(metadata.name, "erc20");
+        assert_eq!(
+            metadata.constructors,
+            [Function {
+                name: "new".to_owned(),
+                selector: "0x9bae9d5e".to_owned(),
+                args: vec![("initial_supply".to_owned(), "u128".to_owned())],
+                return_type: None,
+                payable: false,
+            }]
+        );
+
+        let transfer = &metadata.messages[3];
+        assert_eq!(transfer.name, "transfer");
+        assert_eq!(transfer.selector, "0x84a15da1");
+        assert_eq!(
+            transfer.args,
+            [
+                (
+                    "to".to_owned(),
+                    "ink_env::types::AccountId([u8; 32])".to_owned()
+                ),
+                ("value".to_owned(), "u128".to_owned()),
+            ]
+        );
+        assert_eq!(
+            transfer.return_type.as_deref(),
+            Some(
+                "Result<(), erc20::erc20::Error { InsufficientBalance = 0 | \
+                 InsufficientAllowance = 1 }> { Ok(()) | \
+                 Err(erc20::erc20::Error { InsufficientBalance = 0 | \
+                 InsufficientAllowance = 1 }) }"
+            )
+        );
+
+        let names: Vec<_> = metadata.events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Transfer", "Approval"]);
+        assert_eq!(
+            metadata.events[0].args[0],
+            Topic {
+                name: "from".to_owned(),
+                ty: "Option<ink_env::types::AccountId([u8; 32])> { None | \
+                 Some(ink_env::types::AccountId([u8; 32])) }"
+                    .to_owned(),
+                indexed: true,
+            }
+        );
+
+        let entry = |path: &str| {
+            metadata
+                .storage
+                .iter()
+                .find(|entry| entry.path == path)
+                .unwrap_or_else(|| panic!("no entry {}", path))
+        };
+        assert_eq!(
+            entry("total_supply"),
+            &Entry {
+                path: "total_supply".to_owned(),
+                key: format!("0x{:0<64}", "00"),
+                ty: Some("u128".to_owned()),
+            }
+        );
+        assert_eq!(entry("balances.values").ty, None);
+        assert_eq!(
+            entry("balances.values.[hash]").key,
+            format!("0x{:0<64}", "0300000001")
+        );
+    }
+
+    #[test]
+    fn invalid_metadata_is_an_error() {
+        assert!(matches!(Metadata::parse(b"{"), Err(Error::Json(_))));
+        assert!(matches!(
+            Metadata::parse(b"{\"types\": []}"),
+            Err(Error::Invalid("spec"))
+        ));
+    }
+}